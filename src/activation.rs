@@ -2,13 +2,20 @@ use crate::Layer;
 use crate::tensor::Tensor;
 use crate::tensor::TensorError;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ActivationType {
     ReLU,
+    /// ReLU capped at 6: min(max(x, 0), 6)
+    ReLU6,
     Sigmoid,
     Tanh,
+    Softplus,
+    Mish,
 }
 pub struct Activation {
     input: Tensor,
+    // Forward result, so Tanh's derivative matches exactly what was passed on
+    output: Tensor,
     t: ActivationType,
 }
 
@@ -16,23 +23,21 @@ impl Layer for Activation {
     fn forward(&mut self, input: &Tensor) -> Result<Tensor, TensorError> {
         self.input = Tensor::new(input.data().to_vec(), input.shape().to_vec())?;
 
+        let output = self.forward_infer(input)?;
+        self.output = output.clone();
+        Ok(output)
+    }
+
+    fn forward_infer(&self, input: &Tensor) -> Result<Tensor, TensorError> {
         match self.t {
             ActivationType::ReLU => input.relu(),
-            ActivationType::Sigmoid => {
-                let neg_x = input.scale(&-1.0)?;
-                let denominator = Tensor::one(input.shape().to_vec())?.add(&neg_x.exp()?)?;
-
-                Tensor::one(input.shape().to_vec())?.div(&denominator)
-            }
-            ActivationType::Tanh => {
-                // Formula: (exp(x) - exp(-x)) / (exp(x) + exp(-x))
-                let exp_x = input.exp()?;
-                let exp_neg_x = input.scale(&-1.0)?.exp()?;
-
-                let numerator = exp_x.sub(&exp_neg_x)?;
-                let denominator = exp_x.add(&exp_neg_x)?;
-
-                numerator.div(&denominator)
+            ActivationType::ReLU6 => input.map(|a| a.clamp(0.0, 6.0)),
+            ActivationType::Sigmoid => stable_sigmoid(input),
+            ActivationType::Tanh => input.tanh(),
+            ActivationType::Softplus => input.softplus(),
+            ActivationType::Mish => {
+                // Formula: x * tanh(softplus(x))
+                input.mul(&input.softplus()?.map(f32::tanh)?)
             }
         }
     }
@@ -43,30 +48,43 @@ impl Layer for Activation {
                 let mask = self.input.relu_prime()?;
                 output_error.mul(&mask)
             }
+            ActivationType::ReLU6 => {
+                // Gradient only flows where the input is strictly inside (0, 6)
+                let mask = self.input.map(|a| (a > 0.0 && a < 6.0) as u8 as f32)?;
+                output_error.mul(&mask)
+            }
 
             ActivationType::Sigmoid => {
-                let neg_input = self.input.scale(&-1.0)?;
-                let denominator =
-                    Tensor::one(self.input.shape().to_vec())?.add(&neg_input.exp()?)?;
-                let a = Tensor::one(self.input.shape().to_vec())?.div(&denominator)?;
+                let a = stable_sigmoid(&self.input)?;
 
-                let one = Tensor::one(a.shape().to_vec())?;
-                let sigmoid_prime = a.mul(&one.sub(&a)?)?;
+                let sigmoid_prime = a.mul(&a.rsub_scalar(1.0)?)?;
 
                 output_error.mul(&sigmoid_prime)
             }
             ActivationType::Tanh => {
-                // Derivative: 1 - tanh^2(x)
-                let exp_x = self.input.exp()?;
-                let exp_neg_x = self.input.scale(&-1.0)?.exp()?;
-                let tanh_x = exp_x.sub(&exp_neg_x)?.div(&exp_x.add(&exp_neg_x)?)?;
-
-                let one = Tensor::one(tanh_x.shape().to_vec())?;
-                let tanh_sq = tanh_x.mul(&tanh_x)?;
-                let tanh_prime = one.sub(&tanh_sq)?;
+                // Derivative: 1 - tanh^2(x), using the tanh values forward returned
+                let tanh_sq = self.output.mul(&self.output)?;
+                let tanh_prime = tanh_sq.rsub_scalar(1.0)?;
 
                 output_error.mul(&tanh_prime)
             }
+            ActivationType::Softplus => {
+                // Derivative of softplus is the sigmoid of the input
+                output_error.mul(&stable_sigmoid(&self.input)?)
+            }
+            ActivationType::Mish => {
+                // Derivative: t + x * (1 - t^2) * sigmoid(x), with t = tanh(softplus(x))
+                let t = self.input.softplus()?.map(f32::tanh)?;
+                let sech_sq = t.mul(&t)?.rsub_scalar(1.0)?;
+                let mish_prime = t.add(
+                    &self
+                        .input
+                        .mul(&sech_sq)?
+                        .mul(&stable_sigmoid(&self.input)?)?,
+                )?;
+
+                output_error.mul(&mish_prime)
+            }
         }
     }
 }
@@ -75,7 +93,20 @@ impl Activation {
     pub fn new(t: ActivationType) -> Self {
         Activation {
             input: Tensor::empty(),
+            output: Tensor::empty(),
             t,
         }
     }
 }
+
+/// Sigmoid that never exponentiates a positive number: for x < 0 it uses
+/// exp(x) / (1 + exp(x)), so large logits of either sign cannot overflow
+pub fn stable_sigmoid(input: &Tensor) -> Result<Tensor, TensorError> {
+    input.sigmoid()
+}
+
+/// Softmax over every row of a 2D tensor of logits. The row maximum is subtracted
+/// before exponentiating, so large logits cannot overflow.
+pub fn stable_softmax(input: &Tensor) -> Result<Tensor, TensorError> {
+    input.softmax_rows()
+}
@@ -0,0 +1,196 @@
+use crate::Rng;
+use crate::tensor::{Tensor, TensorError};
+
+/// Shuffles the rows of `x` and `y` with the same Fisher-Yates permutation,
+/// so every input row stays paired with its target row
+pub fn shuffle_rows(
+    x: &Tensor,
+    y: &Tensor,
+    rng: &mut dyn Rng,
+) -> Result<(Tensor, Tensor), TensorError> {
+    if x.shape().is_empty() || y.shape().is_empty() {
+        return Err(TensorError::InvalidRank);
+    }
+
+    let rows = x.shape()[0];
+    if y.shape()[0] != rows {
+        return Err(TensorError::ShapeMismatch);
+    }
+
+    let mut order: Vec<usize> = (0..rows).collect();
+    for i in (1..rows).rev() {
+        let j = (rng.next_u32() as u32 as usize) % (i + 1);
+        order.swap(i, j);
+    }
+
+    Ok((permute_rows(x, &order)?, permute_rows(y, &order)?))
+}
+
+/// Splits a tensor into rows `[0, at)` and `[at, rows)`
+pub fn split_rows(t: &Tensor, at: usize) -> Result<(Tensor, Tensor), TensorError> {
+    if t.shape().is_empty() {
+        return Err(TensorError::InvalidRank);
+    }
+
+    let rows = t.shape()[0];
+    if at > rows {
+        return Err(TensorError::IndexOutOfBounds);
+    }
+
+    let cols = t.data().len() / rows.max(1);
+    let (head, tail) = t.data().split_at(at * cols);
+
+    let mut head_shape = t.shape().to_vec();
+    head_shape[0] = at;
+    let mut tail_shape = t.shape().to_vec();
+    tail_shape[0] = rows - at;
+
+    Ok((
+        Tensor::new(head.to_vec(), head_shape)?,
+        Tensor::new(tail.to_vec(), tail_shape)?,
+    ))
+}
+
+/// Gaussian blobs for multi-class problems: sample `i` is drawn around
+/// `centers[i % centers.len()]` with standard deviation `spread` on both axes.
+/// Returns the `[samples, 2]` points and `[samples, centers.len()]` one-hot labels.
+///
+/// Panics if `centers` is empty.
+pub fn generate_blobs(
+    samples: usize,
+    centers: &[(f32, f32)],
+    spread: f32,
+    rng: &mut dyn Rng,
+) -> (Tensor, Tensor) {
+    assert!(
+        !centers.is_empty(),
+        "generate_blobs needs at least one center"
+    );
+
+    let classes = centers.len();
+    let mut inputs = Vec::with_capacity(samples * 2);
+    let mut targets = vec![0.0; samples * classes];
+
+    for i in 0..samples {
+        let class = i % classes;
+        let (cx, cy) = centers[class];
+        inputs.push(cx + spread * gaussian(rng));
+        inputs.push(cy + spread * gaussian(rng));
+        targets[i * classes + class] = 1.0;
+    }
+
+    (
+        Tensor::new(inputs, vec![samples, 2]).unwrap(),
+        Tensor::new(targets, vec![samples, classes]).unwrap(),
+    )
+}
+
+/// Standard normal sample from two uniform draws (Box-Muller)
+fn gaussian(rng: &mut dyn Rng) -> f32 {
+    // next_f32 is uniform in [-1, 1], so its magnitude is uniform in [0, 1]
+    let u1 = (1.0 - rng.next_f32().abs()).max(f32::MIN_POSITIVE);
+    let u2 = rng.next_f32().abs();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+}
+
+fn permute_rows(t: &Tensor, order: &[usize]) -> Result<Tensor, TensorError> {
+    let cols = t.data().len() / t.shape()[0].max(1);
+
+    let data = order
+        .iter()
+        .flat_map(|&r| t.data()[r * cols..(r + 1) * cols].iter().copied())
+        .collect();
+
+    Tensor::new(data, t.shape().to_vec())
+}
+
+/// Per-column standardization that remembers the statistics of the data it was
+/// fitted on, so test data is scaled exactly like the training data
+#[derive(Debug, Default)]
+pub struct Scaler {
+    mean: Vec<f32>,
+    std: Vec<f32>,
+}
+
+impl Scaler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores the mean and (population) standard deviation of every column of a 2D tensor
+    pub fn fit(&mut self, x: &Tensor) -> Result<(), TensorError> {
+        if x.shape().len() != 2 || x.shape()[0] == 0 || x.shape()[1] == 0 {
+            return Err(TensorError::InvalidRank);
+        }
+
+        let (rows, cols) = (x.shape()[0], x.shape()[1]);
+        let n = rows as f32;
+
+        let mut mean = vec![0.0; cols];
+        for row in x.data().chunks_exact(cols) {
+            for (m, &v) in mean.iter_mut().zip(row) {
+                *m += v / n;
+            }
+        }
+
+        let mut var = vec![0.0; cols];
+        for row in x.data().chunks_exact(cols) {
+            for ((s, &v), &m) in var.iter_mut().zip(row).zip(&mean) {
+                *s += (v - m) * (v - m) / n;
+            }
+        }
+
+        // A constant column has nothing to scale, dividing by 1 keeps it finite
+        self.std = var
+            .into_iter()
+            .map(|v: f32| if v > 0.0 { v.sqrt() } else { 1.0 })
+            .collect();
+        self.mean = mean;
+        Ok(())
+    }
+
+    pub fn mean(&self) -> &[f32] {
+        &self.mean
+    }
+
+    pub fn std(&self) -> &[f32] {
+        &self.std
+    }
+
+    /// Maps every column to (x - mean) / std using the fitted statistics
+    pub fn transform(&self, x: &Tensor) -> Result<Tensor, TensorError> {
+        self.map_columns(x, |v, m, s| (v - m) / s)
+    }
+
+    /// Undoes `transform`: x * std + mean
+    pub fn inverse_transform(&self, x: &Tensor) -> Result<Tensor, TensorError> {
+        self.map_columns(x, |v, m, s| v * s + m)
+    }
+
+    fn map_columns(
+        &self,
+        x: &Tensor,
+        op: impl Fn(f32, f32, f32) -> f32,
+    ) -> Result<Tensor, TensorError> {
+        if x.shape().len() != 2 {
+            return Err(TensorError::InvalidRank);
+        }
+        // Also catches an unfitted scaler, which has no columns
+        if self.mean.is_empty() || x.shape()[1] != self.mean.len() {
+            return Err(TensorError::ShapeMismatch);
+        }
+
+        let data = x
+            .data()
+            .chunks_exact(self.mean.len())
+            .flat_map(|row| {
+                row.iter()
+                    .zip(self.mean.iter().zip(&self.std))
+                    .map(|(&v, (&m, &s))| op(v, m, s))
+                    .collect::<Vec<f32>>()
+            })
+            .collect();
+
+        Tensor::new(data, x.shape().to_vec())
+    }
+}
@@ -1,40 +1,196 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use crate::Rng;
+use crate::examples::image_reconstructor::{ReconstructorConfig, build_network};
 use crate::tensor::Tensor;
 
-pub fn run_benchmark() {
-    let size = [2, 4, 8, 16, 32, 64, 128, 256, 512, 1024];
+/// Timings reported by `run_network_benchmark`
+#[derive(Debug, Clone, Copy)]
+pub struct NetworkBenchmark {
+    /// Average wall time of one forward + backward pass over all samples
+    pub avg_epoch: Duration,
+    /// Samples pushed through forward + backward per second
+    pub samples_per_sec: f64,
+}
 
-    println!();
+/// Square matrix sizes timed by `run_benchmark`
+pub const BENCHMARK_SIZES: [usize; 10] = [2, 4, 8, 16, 32, 64, 128, 256, 512, 1024];
+
+/// Timings of one square size, shared by `run_benchmark` and `run_benchmark_csv`
+struct SizeTimings {
+    matmul_naive: Duration,
+    matmul: Duration,
+    #[cfg(feature = "simd")]
+    matmul_simd: Duration,
+    transpose_naive: Duration,
+    transpose_blocked: Duration,
+}
 
-    for s in size {
-        let a_data = vec![1.0; s * s];
-        let b_data = vec![2.0; s * s];
+impl SizeTimings {
+    fn matmul_speedup(&self) -> f64 {
+        self.matmul_naive.as_secs_f64() / self.matmul.as_secs_f64()
+    }
 
-        let tensor_a = Tensor::new(a_data, vec![s, s]).unwrap();
-        let tensor_b = Tensor::new(b_data, vec![s, s]).unwrap();
+    fn transpose_speedup(&self) -> f64 {
+        self.transpose_naive.as_secs_f64() / self.transpose_blocked.as_secs_f64()
+    }
+}
 
-        println!("Benchmarking {}x{} Matrix Multiplication...", s, s);
+/// Runs every variant once on `s x s` matrices, asserting that they agree
+fn time_size(s: usize) -> SizeTimings {
+    let tensor_a = Tensor::new(vec![1.0; s * s], vec![s, s]).unwrap();
+    let tensor_b = Tensor::new(vec![2.0; s * s], vec![s, s]).unwrap();
+
+    let start_naive = Instant::now();
+    let res_naive = tensor_a.matmul_naive(&tensor_b).expect("Naive failed");
+    let matmul_naive = start_naive.elapsed();
 
-        // 2. Benchmark Naive Method
-        let start_naive = Instant::now();
-        let _res_naive = tensor_a.matmul_naive(&tensor_b).expect("Naive failed");
-        let duration_naive = start_naive.elapsed();
-        println!("Time taken (naive):     {:?}", duration_naive);
+    let start_opt = Instant::now();
+    let res_opt = tensor_a.matmul(&tensor_b).expect("Optimized failed");
+    let matmul = start_opt.elapsed();
 
-        // 3. Benchmark Optimized Method
-        let start_opt = Instant::now();
-        let _res_opt = tensor_a.matmul(&tensor_b).expect("Optimized failed");
-        let duration_opt = start_opt.elapsed();
-        println!("Time taken (optimized): {:?}", duration_opt);
+    assert_eq!(res_naive, res_opt);
+
+    #[cfg(feature = "simd")]
+    let matmul_simd = {
+        let start_simd = Instant::now();
+        let res_simd = tensor_a.matmul_simd(&tensor_b).expect("SIMD failed");
+        let duration_simd = start_simd.elapsed();
+        assert_eq!(res_opt, res_simd);
+        duration_simd
+    };
+
+    let start_naive = Instant::now();
+    let tr_naive = tensor_a.transpose_naive().expect("Naive failed");
+    let transpose_naive = start_naive.elapsed();
+
+    let start_blocked = Instant::now();
+    let tr_blocked = tensor_a.transpose_blocked().expect("Blocked failed");
+    let transpose_blocked = start_blocked.elapsed();
+
+    assert_eq!(tr_naive, tr_blocked);
+
+    SizeTimings {
+        matmul_naive,
+        matmul,
+        #[cfg(feature = "simd")]
+        matmul_simd,
+        transpose_naive,
+        transpose_blocked,
+    }
+}
+
+pub fn run_benchmark() {
+    println!();
 
-        assert_eq!(_res_naive, _res_opt);
+    for s in BENCHMARK_SIZES {
+        let timings = time_size(s);
 
+        println!("Benchmarking {}x{} Matrix Multiplication...", s, s);
+        println!("Time taken (naive):     {:?}", timings.matmul_naive);
+        println!("Time taken (optimized): {:?}", timings.matmul);
         println!("Results match!");
+        println!(
+            "Speedup factor:         {:.2}x faster",
+            timings.matmul_speedup()
+        );
+        println!();
 
-        // 4. Calculate Speedup
-        let speedup = duration_naive.as_secs_f64() / duration_opt.as_secs_f64();
-        println!("Speedup factor:         {:.2}x faster", speedup);
+        #[cfg(feature = "simd")]
+        {
+            println!("Time taken (simd):      {:?}", timings.matmul_simd);
+            let speedup = timings.matmul.as_secs_f64() / timings.matmul_simd.as_secs_f64();
+            println!("Speedup over optimized: {:.2}x faster", speedup);
+            println!();
+        }
+
+        println!("Benchmarking {}x{} Transpose...", s, s);
+        println!("Time taken (naive):     {:?}", timings.transpose_naive);
+        println!("Time taken (blocked):   {:?}", timings.transpose_blocked);
+        println!("Results match!");
+        println!(
+            "Speedup factor:         {:.2}x faster",
+            timings.transpose_speedup()
+        );
         println!();
     }
 }
+
+/// Same timings as `run_benchmark`, returned as CSV: a `size,naive_ns,optimized_ns,speedup,
+/// transpose_naive_ns,transpose_blocked_ns,transpose_speedup` header, then one row per
+/// size, with fields joined by `separator`
+pub fn run_benchmark_csv(sizes: &[usize], separator: char) -> String {
+    let separator = separator.to_string();
+    let header = [
+        "size",
+        "naive_ns",
+        "optimized_ns",
+        "speedup",
+        "transpose_naive_ns",
+        "transpose_blocked_ns",
+        "transpose_speedup",
+    ];
+    let mut csv = header.join(&separator);
+    csv.push('\n');
+
+    for &s in sizes {
+        let timings = time_size(s);
+        let fields = [
+            s.to_string(),
+            timings.matmul_naive.as_nanos().to_string(),
+            timings.matmul.as_nanos().to_string(),
+            format!("{:.3}", timings.matmul_speedup()),
+            timings.transpose_naive.as_nanos().to_string(),
+            timings.transpose_blocked.as_nanos().to_string(),
+            format!("{:.3}", timings.transpose_speedup()),
+        ];
+        csv.push_str(&fields.join(&separator));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Trains the image reconstructor network for `passes` epochs on `samples` random
+/// coordinates and reports the average epoch time and throughput. The prediction
+/// afterwards must be finite, so this doubles as a smoke test of the training path.
+pub fn run_network_benchmark(
+    rng: &mut dyn Rng,
+    samples: usize,
+    passes: usize,
+) -> Result<NetworkBenchmark, Box<dyn std::error::Error>> {
+    let mut nn = build_network(&ReconstructorConfig::default(), rng)?;
+
+    let x: Vec<f32> = (0..samples * 2).map(|_| rng.next_f32().abs()).collect();
+    let y: Vec<f32> = (0..samples)
+        .map(|_| (rng.next_f32() > 0.0) as u8 as f32)
+        .collect();
+    let x = Tensor::new(x, vec![samples, 2])?;
+    let y = Tensor::new(y, vec![samples, 1])?;
+
+    println!(
+        "Benchmarking {} forward + backward passes over {} samples...",
+        passes, samples
+    );
+
+    let report = nn.fit_with_report(&x, &y, passes, 0.01)?;
+
+    let output = nn.predict(&x)?;
+    assert!(
+        output.data().iter().all(|v| v.is_finite()),
+        "Network output is not finite"
+    );
+
+    let avg_epoch = report.elapsed / passes.max(1) as u32;
+
+    println!("Average epoch time:     {:?}", avg_epoch);
+    println!(
+        "Throughput:             {:.0} samples/sec",
+        report.samples_per_sec
+    );
+    println!();
+
+    Ok(NetworkBenchmark {
+        avg_epoch,
+        samples_per_sec: report.samples_per_sec,
+    })
+}
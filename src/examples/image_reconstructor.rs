@@ -1,23 +1,63 @@
 use std::time::Instant;
 
-use crate::image_utils::{read_pbm_for_nn, render_image};
+use crate::image_utils::{
+    read_pbm_for_nn, render_image, render_image_with_threshold, save_as_pbm_with_threshold,
+};
 use crate::neural_network::Network;
-use crate::tensor::TensorError;
 use crate::{
     Rng,
-    activation::{Activation, ActivationType},
-    linear::Linear,
-    loss::bce_sigmoid_delta,
+    activation::ActivationType,
+    linear::DEFAULT_INIT,
+    loss::{bce_loss, bce_sigmoid_delta},
     neural_network::NetworkBuilder,
-    tensor::Tensor,
+    tensor::{Tensor, TensorError},
 };
 
+/// Training runs in rounds of this many epochs, with progress printed in between
+const EPOCHS_PER_ROUND: usize = 1000;
+/// Epochs between two loss reports inside a round
+const LOSS_REPORT_EVERY: usize = 250;
+
+/// Architecture and training settings of the coordinate-to-pixel network
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconstructorConfig {
+    /// Width of each hidden layer, each a Linear followed by `activation`,
+    /// so the length is the depth of the network
+    pub hidden: Vec<usize>,
+    /// Activation of the hidden layers, the output layer always ends in a Sigmoid
+    pub activation: ActivationType,
+    /// Total training epochs, run in rounds of 1000
+    pub epochs: usize,
+    /// Learning rate of the first rounds, lowered by 0.002 every 10 rounds down to 0.001
+    pub lr: f32,
+    /// Rows per weight update, `None` trains on the whole image at once
+    pub batch: Option<usize>,
+}
+
+impl Default for ReconstructorConfig {
+    /// The architecture the example always used: five Tanh hidden layers of 64, 64,
+    /// an expansion to 128, a contraction back to 64 and a narrowing to 32
+    fn default() -> Self {
+        Self {
+            hidden: vec![64, 64, 128, 64, 32],
+            activation: ActivationType::Tanh, // For Image reconstruction tasks, Tanh is a better solution
+            epochs: 25_000,
+            lr: 0.1,
+            batch: None,
+        }
+    }
+}
+
+/// `threshold` decides which predicted intensities count as set pixels,
+/// both in the terminal preview and in the saved PBM files
 pub fn reconstruct_image(
     source: &str,
     size: usize,
+    threshold: f32,
+    config: &ReconstructorConfig,
     rng: &mut dyn Rng,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let (w, h, x_data, y_data) = read_pbm_for_nn(source);
+    let (w, h, x_data, y_data) = read_pbm_for_nn(source)?;
 
     // Without Normalization, the gradient becomes zero. So, we make the data between 0 and 1
     let normalized_x_train: Vec<f32> = x_data
@@ -28,26 +68,11 @@ pub fn reconstruct_image(
     let x_train = Tensor::new(normalized_x_train.clone(), vec![h * w, 2])?;
     let y_train = Tensor::new(y_data, vec![h * w, 1])?;
 
-    let hl = 64; // Hidden layer size
-    let mut nn = NetworkBuilder::new()
-        .add_layer(Box::new(Linear::new(2, hl, rng)))
-        .add_layer(Box::new(Activation::new(ActivationType::Tanh))) // For Image reconstruction tasks, Tanh is a better solution
-        .add_layer(Box::new(Linear::new(hl, hl, rng)))
-        .add_layer(Box::new(Activation::new(ActivationType::Tanh)))
-        .add_layer(Box::new(Linear::new(hl, 2 * hl, rng))) // Expansion layer
-        .add_layer(Box::new(Activation::new(ActivationType::Tanh)))
-        .add_layer(Box::new(Linear::new(2 * hl, hl, rng))) // Contraction layer
-        .add_layer(Box::new(Activation::new(ActivationType::Tanh)))
-        .add_layer(Box::new(Linear::new(hl, hl / 2, rng)))
-        .add_layer(Box::new(Activation::new(ActivationType::Tanh)))
-        .add_layer(Box::new(Linear::new(hl / 2, 1, rng)))
-        .add_layer(Box::new(Activation::new(ActivationType::Sigmoid))) // Final Sigmoid for pixel intensity
-        .loss_gradient(bce_sigmoid_delta)
-        .build()
-        .map_err(|e| e.to_string())?;
+    let mut nn = build_network(config, rng)?;
+    let batches = split_batches(&x_train, &y_train, config.batch)?;
 
-    let total_epochs = 25;
-    let mut learning_rate = 0.1;
+    let total_epochs = config.epochs.div_ceil(EPOCHS_PER_ROUND);
+    let mut learning_rate = config.lr;
 
     // To perform back of the envelop calculation on how much time is required
     let mut last_checkpoint = Instant::now();
@@ -62,17 +87,34 @@ pub fn reconstruct_image(
                 false => learning_rate,
             }
         }
-        nn.fit(&x_train, &y_train, 1000, learning_rate)?;
+        for e in 1..=EPOCHS_PER_ROUND {
+            // Row-weighted mean of the batch losses, the full-image loss for a single batch
+            let mut loss = 0.0;
+            for (x_batch, y_batch) in &batches {
+                let batch_loss = nn.fit_with_history(x_batch, y_batch, 1, learning_rate)?[0];
+                loss += batch_loss * x_batch.shape()[0] as f32 / (h * w) as f32;
+            }
+
+            if e % LOSS_REPORT_EVERY == 0 {
+                println!("  epoch {:>4}/{}  loss: {:.6}", e, EPOCHS_PER_ROUND, loss);
+            }
+        }
 
         if epoch % 5 == 0 {
             println!("Reconstruction at epoch {epoch}");
 
             println!("Original Image:");
             // We use the original data for comparison
-            render_image(w, h, &y_train.data());
+            render_image(w, h, y_train.data());
 
             println!("Rescaled Network Drawing after epoch {}:", epoch * 1000);
-            draw_save_network_image(size, &mut nn, &format!("output/reconstructed_{epoch}.pbm"))?;
+            draw_save_network_image(
+                size,
+                (w, h),
+                &mut nn,
+                &format!("output/reconstructed_{epoch}.pbm"),
+                threshold,
+            )?;
 
             // Trace time
             let duration = last_checkpoint.elapsed();
@@ -94,30 +136,110 @@ pub fn reconstruct_image(
     }
     println!("Original Image:");
     // We use the original data for comparison
-    render_image(w, h, &y_train.data());
+    render_image(w, h, y_train.data());
     println!("Final Image Reconstruction");
-    draw_save_network_image(size, &mut nn, &format!("output/reconstructed_final.pbm"))?;
+    draw_save_network_image(
+        size,
+        (w, h),
+        &mut nn,
+        "output/reconstructed_final.pbm",
+        threshold,
+    )?;
 
     Ok(())
 }
 
-fn draw_save_network_image(size: usize, nn: &mut Network, dest: &str) -> Result<(), TensorError> {
+/// The coordinate-to-pixel network: [row, col] in, pixel intensity out.
+/// One hidden layer per entry of `config.hidden`, so `2 * (hidden.len() + 1)` layers in total.
+pub fn build_network(config: &ReconstructorConfig, rng: &mut dyn Rng) -> Result<Network, String> {
+    let spec: Vec<_> = config
+        .hidden
+        .iter()
+        .map(|&width| (width, config.activation))
+        .chain([(1, ActivationType::Sigmoid)]) // Final Sigmoid for pixel intensity
+        .collect();
+
+    NetworkBuilder::from_spec_with_init(&spec, 2, DEFAULT_INIT, rng)
+        .loss_gradient(bce_sigmoid_delta)
+        .loss(bce_loss)
+        .build()
+}
+
+/// Consecutive `(x, y)` chunks of `batch` rows (the last one may be shorter),
+/// or the whole data set as a single chunk for `None`
+fn split_batches(
+    x: &Tensor,
+    y: &Tensor,
+    batch: Option<usize>,
+) -> Result<Vec<(Tensor, Tensor)>, TensorError> {
+    let Some(batch) = batch.filter(|&b| b > 0) else {
+        return Ok(vec![(x.clone(), y.clone())]);
+    };
+
+    // Rows `start..end` of a 2D tensor, copied straight out of its data
+    let rows_of = |t: &Tensor, start: usize, end: usize| {
+        let cols = t.shape()[1];
+        Tensor::new(
+            t.data()[start * cols..end * cols].to_vec(),
+            vec![end - start, cols],
+        )
+    };
+
+    let rows = x.shape()[0];
+    (0..rows)
+        .step_by(batch)
+        .map(|start| {
+            let end = (start + batch).min(rows);
+            Ok((rows_of(x, start, end)?, rows_of(y, start, end)?))
+        })
+        .collect()
+}
+
+/// Normalized `[row, col]` inputs for a `size x size` rendering of an image trained at
+/// `train_dims` (w, h). Destination pixels are spread over the training pixel grid and
+/// normalized by the training dimensions, so they cover the same range the network saw
+/// during training, whatever the output size.
+pub fn destination_coords(size: usize, train_dims: (usize, usize)) -> Vec<f32> {
+    let (w, h) = train_dims;
+    // Position of destination pixel `i` on a training axis of `len` pixels, normalized by `len`
+    let to_train = |i: usize, len: usize| {
+        let last = len.saturating_sub(1) as f32;
+        let pos = if size > 1 {
+            i as f32 * last / (size - 1) as f32
+        } else {
+            0.0
+        };
+        pos / len.max(1) as f32
+    };
+
     let mut dest_coords = Vec::with_capacity(size * size * 2);
     for r in 0..size {
         for c in 0..size {
-            // Normalization here too
-            dest_coords.push(r as f32 / size as f32);
-            dest_coords.push(c as f32 / size as f32);
+            dest_coords.push(to_train(r, h));
+            dest_coords.push(to_train(c, w));
         }
     }
+    dest_coords
+}
+
+/// Renders the network's `size x size` reconstruction of an image trained at
+/// `train_dims` (w, h) and saves it to `dest`, with pixels at or above `threshold` set
+pub fn draw_save_network_image(
+    size: usize,
+    train_dims: (usize, usize),
+    nn: &mut Network,
+    dest: &str,
+    threshold: f32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let dest_coords = destination_coords(size, train_dims);
 
     let x_dest = Tensor::new(dest_coords, vec![size * size, 2])?;
     let prediction = nn.forward(x_dest)?;
 
-    render_image(size, size, prediction.data());
+    render_image_with_threshold(size, size, prediction.data(), threshold);
 
     // Save the result to a file
-    crate::image_utils::save_as_pbm(dest, size, size, prediction.data());
+    save_as_pbm_with_threshold(dest, size, size, prediction.data(), threshold, true)?;
     println!("Saved reconstructed image to {}", dest);
 
     Ok(())
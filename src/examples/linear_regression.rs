@@ -2,10 +2,12 @@ use crate::{
     Layer, Rng,
     linear::Linear,
     loss::{mse_loss, mse_loss_gradient},
+    metrics::mape,
     tensor::{Tensor, TensorError},
 };
 
-pub fn linear_regression(rng: &mut dyn Rng) -> Result<(), TensorError> {
+/// Returns the final prediction and the final MSE loss
+pub fn linear_regression(rng: &mut dyn Rng) -> Result<(Tensor, f32), TensorError> {
     let mut linear = Linear::new(2, 1, rng);
 
     println!("Initial Weights:");
@@ -51,6 +53,11 @@ pub fn linear_regression(rng: &mut dyn Rng) -> Result<(), TensorError> {
     println!("Final MSE Loss after {epochs} iterations:");
     println!("{}", loss);
 
+    println!(
+        "Mean Absolute Percentage Error: {:.2}%",
+        mape(&output, &actual)?
+    );
+
     println!("Final weights");
     println!("{}", linear.weight());
 
@@ -60,5 +67,5 @@ pub fn linear_regression(rng: &mut dyn Rng) -> Result<(), TensorError> {
     println!("Actual Output:");
     println!("{}", actual);
 
-    Ok(())
+    Ok((output, loss.data()[0]))
 }
@@ -1,9 +1,20 @@
-use std::{thread, time::Duration};
+use crate::{Layer, Rng, examples::AnimationConfig, image_utils::{Palette, PlotColor, Trace, render_plot_palette}, linear::Linear, loss::{mse_loss, mse_loss_gradient}, tensor::{Tensor, TensorError}};
 
-use crate::{Layer, Rng, image_utils::{PlotColor, Trace, render_plot}, linear::Linear, loss::{mse_loss, mse_loss_gradient}, tensor::{Tensor, TensorError}};
+/// Playback used by the linear regression animation
+pub fn animation_config() -> AnimationConfig {
+    AnimationConfig {
+        epochs: 15_000,
+        render_every: 10,
+        frame_delay_ms: 3,
+        width: 100,
+        height: 35,
+        frame_dir: None,
+        palette: Palette::Standard,
+    }
+}
 
-pub fn linear_regression(rng: &mut dyn Rng) -> Result<(), TensorError> {
-    let mut linear = Linear::new(2, 1, rng);
+pub fn linear_regression(rng: &mut dyn Rng, config: &AnimationConfig) -> Result<(), TensorError> {
+    let mut linear = Linear::new_uniform(2, 1, rng);
 
     let far_weights = Tensor::new(vec![-2.0, 25.0], vec![2, 1])?; 
     linear.set_weight(far_weights); 
@@ -36,16 +47,15 @@ pub fn linear_regression(rng: &mut dyn Rng) -> Result<(), TensorError> {
     }
     let line_input = Tensor::new(line_input_vec, vec![50, 2])?;
 
-    let epochs = 15_000;
     let bounds = Some((0.0, 20.0, 0.0, 50.0)); 
-    for epoch in 0..epochs {
+    for epoch in 0..config.epochs {
         let predicted = linear.forward(&input)?;
         let loss_val = mse_loss(&predicted, &actual)?.data()[0];
 
         let grad = mse_loss_gradient(&predicted, &actual)?;
         linear.backward(&grad, 0.0005)?;
 
-        if epoch % 10 == 0 {
+        if config.should_render(epoch) {
             print!("\x1b[2J\x1b[1;1H");
             let line_pred = linear.forward(&line_input)?;
 
@@ -67,8 +77,8 @@ pub fn linear_regression(rng: &mut dyn Rng) -> Result<(), TensorError> {
                 hide_axes: false
             };
 
-            render_plot(&[trace_actual, trace_pred], 100, 35, bounds, String::from("Linear Regression"));
-            thread::sleep(Duration::from_millis(3));
+            render_plot_palette(&[trace_actual, trace_pred], config.width, config.height, bounds, String::from("Linear Regression"), config.palette);
+            config.wait_frame();
         }
     }
     Ok(())
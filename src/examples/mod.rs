@@ -1,3 +1,7 @@
+use crate::image_utils::{Palette, PlotColor, Trace, save_as_pbm};
+use crate::neural_network::Network;
+use crate::tensor::{Tensor, TensorError};
+
 pub mod linear_regression;
 pub mod linear_regression_animated;
 pub mod neural_network_logic;
@@ -8,4 +12,222 @@ pub mod image_reconstructor;
 pub mod neural_network_not_animated;
 pub mod benchmark;
 pub mod two_moons_3layers;
-pub mod two_moons;
\ No newline at end of file
+pub mod two_moons;
+
+/// Playback settings shared by the animated examples
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationConfig {
+    /// Total number of training epochs
+    pub epochs: usize,
+    /// Draw a frame every `render_every` epochs
+    pub render_every: usize,
+    /// Pause after each frame, in milliseconds
+    pub frame_delay_ms: u64,
+    /// Plot width in terminal columns
+    pub width: usize,
+    /// Plot height in terminal rows
+    pub height: usize,
+    /// When set, every rendered frame is also saved as a numbered PBM in this directory
+    pub frame_dir: Option<String>,
+    /// Colors of the rendered plots
+    pub palette: Palette,
+}
+
+impl Default for AnimationConfig {
+    /// The XOR decision boundary playback
+    fn default() -> Self {
+        Self {
+            epochs: 25_000,
+            render_every: 100,
+            frame_delay_ms: 10,
+            width: 70,
+            height: 25,
+            frame_dir: None,
+            palette: Palette::Standard,
+        }
+    }
+}
+
+impl AnimationConfig {
+    /// Whether the given epoch should produce a frame (only epoch 0 when `render_every` is 0)
+    pub fn should_render(&self, epoch: usize) -> bool {
+        epoch.is_multiple_of(self.render_every)
+    }
+
+    /// Sleeps for the configured frame delay
+    pub fn wait_frame(&self) {
+        std::thread::sleep(std::time::Duration::from_millis(self.frame_delay_ms));
+    }
+}
+
+/// Samples a `(nx + 1) x (ny + 1)` grid over the given ranges and splits the points into
+/// "Predict 1" (cyan) and "Predict 0" (magenta) region traces based on `predict_fn`,
+/// which maps an `(x, y)` coordinate to a class probability.
+pub fn decision_grid<F>(
+    mut predict_fn: F,
+    x_range: (f32, f32),
+    y_range: (f32, f32),
+    nx: usize,
+    ny: usize,
+) -> Result<Vec<Trace>, TensorError>
+where
+    F: FnMut(f32, f32) -> Result<f32, TensorError>,
+{
+    let (mut cyan_x, mut cyan_y, mut magenta_x, mut magenta_y) = (vec![], vec![], vec![], vec![]);
+
+    for gx in 0..=nx {
+        for gy in 0..=ny {
+            let x = x_range.0 + (gx as f32 / nx.max(1) as f32) * (x_range.1 - x_range.0);
+            let y = y_range.0 + (gy as f32 / ny.max(1) as f32) * (y_range.1 - y_range.0);
+
+            if predict_fn(x, y)? > 0.5 {
+                cyan_x.push(x);
+                cyan_y.push(y);
+            } else {
+                magenta_x.push(x);
+                magenta_y.push(y);
+            }
+        }
+    }
+
+    Ok(vec![
+        Trace {
+            name: "Predict 1".into(),
+            x: cyan_x,
+            y: cyan_y,
+            color: PlotColor::Cyan,
+            is_line: false,
+            hide_axes: false,
+        },
+        Trace {
+            name: "Predict 0".into(),
+            x: magenta_x,
+            y: magenta_y,
+            color: PlotColor::Magenta,
+            is_line: false,
+            hide_axes: false,
+        },
+    ])
+}
+
+/// Shade of a decision-region point: cyan for class 1 and magenta for class 0, like
+/// `decision_grid`, but bold when the probability is within 0.1 of the class and dim
+/// when it is within 0.1 of the 0.5 boundary
+pub fn confidence_band(p: f32) -> PlotColor {
+    match p {
+        p if p >= 0.9 => PlotColor::BoldCyan,
+        p if p > 0.6 => PlotColor::Cyan,
+        p if p > 0.5 => PlotColor::DimCyan,
+        p if p >= 0.4 => PlotColor::DimMagenta,
+        p if p > 0.1 => PlotColor::Magenta,
+        _ => PlotColor::BoldMagenta,
+    }
+}
+
+/// Legend text of a `confidence_band` shade
+fn confidence_label(color: PlotColor) -> &'static str {
+    match color {
+        PlotColor::BoldCyan => "class 1 (confident)",
+        PlotColor::Cyan => "class 1",
+        PlotColor::DimCyan => "class 1 (uncertain)",
+        PlotColor::DimMagenta => "class 0 (uncertain)",
+        PlotColor::Magenta => "class 0",
+        _ => "class 0 (confident)",
+    }
+}
+
+/// Same grid as `decision_grid`, with one trace per `confidence_band` that has points,
+/// so the plot shows where the network is unsure instead of only which side it picks
+pub fn confidence_grid<F>(
+    mut predict_fn: F,
+    x_range: (f32, f32),
+    y_range: (f32, f32),
+    nx: usize,
+    ny: usize,
+) -> Result<Vec<Trace>, TensorError>
+where
+    F: FnMut(f32, f32) -> Result<f32, TensorError>,
+{
+    let mut traces: Vec<Trace> = Vec::new();
+
+    for gx in 0..=nx {
+        for gy in 0..=ny {
+            let x = x_range.0 + (gx as f32 / nx.max(1) as f32) * (x_range.1 - x_range.0);
+            let y = y_range.0 + (gy as f32 / ny.max(1) as f32) * (y_range.1 - y_range.0);
+
+            let color = confidence_band(predict_fn(x, y)?);
+            match traces.iter_mut().find(|t| t.color == color) {
+                Some(trace) => {
+                    trace.x.push(x);
+                    trace.y.push(y);
+                }
+                None => traces.push(Trace {
+                    name: confidence_label(color).to_string(),
+                    x: vec![x],
+                    y: vec![y],
+                    color,
+                    is_line: false,
+                    hide_axes: false,
+                }),
+            }
+        }
+    }
+
+    Ok(traces)
+}
+
+/// Evaluates `nn` at the centre of every pixel of a `width x height` grid spanning
+/// `bounds` (min_x, max_x, min_y, max_y) and saves the thresholded classes as a PBM.
+/// Each grid point is fed as `[x, y, 1.0]`, the bias-carrying layout of the classification
+/// examples. The first image row is the top of the plot (max_y).
+pub fn save_decision_boundary(
+    nn: &Network,
+    bounds: (f32, f32, f32, f32),
+    resolution: (usize, usize),
+    path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (width, height) = resolution;
+
+    let coords = pixel_centres(bounds, resolution)
+        .flat_map(|(x, y)| [x, y, 1.0])
+        .collect();
+
+    let grid = Tensor::new(coords, vec![width * height, 3])?;
+    let prediction = nn.predict(&grid)?;
+
+    save_as_pbm(path, width, height, prediction.data())?;
+    Ok(())
+}
+
+/// Same pixel grid as `save_decision_boundary`, but the probabilities come from
+/// `predict_fn`, so examples that drive their layers by hand can dump frames too.
+/// Returns the `width * height` values in image (row-major, top row first) order.
+pub fn decision_frame<F>(
+    mut predict_fn: F,
+    bounds: (f32, f32, f32, f32),
+    resolution: (usize, usize),
+) -> Result<Vec<f32>, TensorError>
+where
+    F: FnMut(f32, f32) -> Result<f32, TensorError>,
+{
+    pixel_centres(bounds, resolution)
+        .map(|(x, y)| predict_fn(x, y))
+        .collect()
+}
+
+/// Plot coordinates of the centre of every pixel, row by row from the top (max_y)
+fn pixel_centres(
+    bounds: (f32, f32, f32, f32),
+    resolution: (usize, usize),
+) -> impl Iterator<Item = (f32, f32)> {
+    let (min_x, max_x, min_y, max_y) = bounds;
+    let (width, height) = resolution;
+
+    (0..height).flat_map(move |r| {
+        (0..width).map(move |c| {
+            let x = min_x + (c as f32 + 0.5) / width as f32 * (max_x - min_x);
+            let y = max_y - (r as f32 + 0.5) / height as f32 * (max_y - min_y);
+            (x, y)
+        })
+    })
+}
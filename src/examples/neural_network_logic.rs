@@ -2,7 +2,7 @@ use crate::{
     Layer, Rng,
     activation::{Activation, ActivationType},
     linear::Linear,
-    loss::bce_sigmoid_delta,
+    loss::{bce_sigmoid_delta, mse_loss},
     tensor::{Tensor, TensorError},
 };
 
@@ -13,7 +13,8 @@ pub enum Gate {
     NAND,
 }
 
-pub fn demonstrate_logic(rng: &mut dyn Rng, gate: Gate) -> Result<(), TensorError> {
+/// Returns the final prediction and the final MSE loss against the gate's truth table
+pub fn demonstrate_logic(rng: &mut dyn Rng, gate: Gate) -> Result<(Tensor, f32), TensorError> {
     let mut linear_layer = Linear::new(3, 1, rng);
 
     let mut activation_layer = Activation::new(ActivationType::Sigmoid);
@@ -54,5 +55,65 @@ pub fn demonstrate_logic(rng: &mut dyn Rng, gate: Gate) -> Result<(), TensorErro
     println!("Model Output after training");
     println!("{}", model_output);
 
-    Ok(())
+    let loss = mse_loss(&model_output, &actual)?.data()[0];
+
+    Ok((model_output, loss))
+}
+
+/// Trains one network with two sigmoid outputs, AND in column 0 and OR in column 1,
+/// on the same inputs. Returns the final `[4, 2]` prediction and the accuracy of each output.
+pub fn demonstrate_multi_gate(rng: &mut dyn Rng) -> Result<(Tensor, Vec<f32>), TensorError> {
+    let mut linear_layer = Linear::new(3, 2, rng);
+
+    let mut activation_layer = Activation::new(ActivationType::Sigmoid);
+
+    let input = Tensor::new(
+        vec![
+            0.0, 0.0, 1.0_f32, 0.0, 1.0, 1.0_f32, 1.0, 0.0, 1.0_f32, 1.0, 1.0, 1.0_f32,
+        ],
+        vec![4, 3],
+    )?;
+    // One row per input, [AND, OR]
+    let actual = Tensor::new(vec![0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0], vec![4, 2])?;
+
+    let learning_rate = 0.1;
+
+    println!("Input:");
+    println!("{}", input);
+
+    println!("Actual Output (AND, OR)");
+    println!("{}", actual);
+
+    for _ in 0..10000 {
+        let linear_output = linear_layer.forward(&input)?;
+        let activation_output = activation_layer.forward(&linear_output)?;
+
+        // bce_sigmoid_delta works element-wise, so both outputs train at once
+        let delta = bce_sigmoid_delta(&activation_output, &actual)?;
+
+        linear_layer.backward(&delta, learning_rate)?;
+    }
+
+    let model_output = linear_layer.forward(&input)?;
+    let model_output = activation_layer.forward(&model_output)?;
+
+    println!("Model Output after training");
+    println!("{}", model_output);
+
+    let mut accuracy = vec![0.0; 2];
+    for (predicted, target) in model_output.rows().zip(actual.rows()) {
+        for (acc, (&p, &t)) in accuracy.iter_mut().zip(predicted.iter().zip(target)) {
+            if (p >= 0.5) == (t == 1.0) {
+                *acc += 0.25;
+            }
+        }
+    }
+
+    println!(
+        "Accuracy - AND: {:.0}%, OR: {:.0}%",
+        accuracy[0] * 100.0,
+        accuracy[1] * 100.0
+    );
+
+    Ok((model_output, accuracy))
 }
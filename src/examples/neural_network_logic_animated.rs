@@ -1,13 +1,12 @@
 use crate::{
     Layer, Rng,
     activation::{Activation, ActivationType},
-    image_utils::{PlotColor, Trace, render_plot},
+    examples::{AnimationConfig, decision_grid},
+    image_utils::{Palette, PlotColor, Trace, render_plot_palette},
     linear::Linear,
     loss::bce_sigmoid_delta,
     tensor::{Tensor, TensorError},
 };
-use std::thread;
-use std::time::Duration;
 
 #[derive(Debug)]
 pub enum AnimatedGate {
@@ -17,8 +16,25 @@ pub enum AnimatedGate {
     NOR,
 }
 
-pub fn demonstrate_logic(rng: &mut dyn Rng, gate: AnimatedGate) -> Result<(), TensorError> {
-    let mut linear_layer = Linear::new(3, 1, rng);
+/// Playback used by the animated gate demonstrations
+pub fn animation_config() -> AnimationConfig {
+    AnimationConfig {
+        epochs: 8000,
+        render_every: 15,
+        frame_delay_ms: 10,
+        width: 70,
+        height: 25,
+        frame_dir: None,
+        palette: Palette::Standard,
+    }
+}
+
+pub fn demonstrate_logic(
+    rng: &mut dyn Rng,
+    gate: AnimatedGate,
+    config: &AnimationConfig,
+) -> Result<(), TensorError> {
+    let mut linear_layer = Linear::new_uniform(3, 1, rng);
 
     let weight_init = match gate {
         AnimatedGate::AND => vec![10.0, -100.0, -15.0],
@@ -46,62 +62,23 @@ pub fn demonstrate_logic(rng: &mut dyn Rng, gate: AnimatedGate) -> Result<(), Te
     let learning_rate = 0.015;
     let bounds = Some((0.0, 20.0, 0.0, 20.0));
 
-    for epoch in 0..8000 {
+    for epoch in 0..config.epochs {
         let linear_output = linear_layer.forward(&input)?;
         let activation_output = activation_layer.forward(&linear_output)?;
 
-        if epoch % 15 == 0 {
-            let mut traces = Vec::new();
+        if config.should_render(epoch) {
+            let mut traces = gate_regions(&linear_layer)?;
             let w = linear_layer.weight().data();
             let w1 = w[0];
             let w2 = w[1];
             let b = w[2];
 
-            let mut cyan_x = Vec::new();
-            let mut cyan_y = Vec::new();
-            let mut magenta_x = Vec::new();
-            let mut magenta_y = Vec::new();
-
-            for gx in (0..=20).step_by(2) {
-                for gy in (0..=20).step_by(2) {
-                    let x = gx as f32;
-                    let y = gy as f32;
-
-                    let decision = w1 * x + w2 * y + b;
-                    if decision > 0.0 {
-                        cyan_x.push(x);
-                        cyan_y.push(y);
-                    } else {
-                        magenta_x.push(x);
-                        magenta_y.push(y);
-                    }
-                }
-            }
-
-            traces.push(Trace {
-                name: "Predict 1".to_string(),
-                x: cyan_x,
-                y: cyan_y,
-                color: PlotColor::Cyan,
-                is_line: false,
-                hide_axes: false
-            });
-
-            traces.push(Trace {
-                name: "Predict 0".to_string(),
-                x: magenta_x,
-                y: magenta_y,
-                color: PlotColor::Magenta,
-                is_line: false,
-                hide_axes: false
-            });
-
             let mut x_line = Vec::new();
             let mut y_line = Vec::new();
             for i in 0..=20 {
                 let x = i as f32;
                 let y = -(w1 * x + b) / w2;
-                if y >= -2.0 && y <= 22.0 {
+                if (-2.0..=22.0).contains(&y) {
                     x_line.push(x);
                     y_line.push(y);
                 }
@@ -135,14 +112,15 @@ pub fn demonstrate_logic(rng: &mut dyn Rng, gate: AnimatedGate) -> Result<(), Te
                 });
             }
 
-            render_plot(
+            render_plot_palette(
                 &traces,
-                70,
-                25,
+                config.width,
+                config.height,
                 bounds,
                 format!("Binary Classification({:?} Gate)", gate),
+                config.palette,
             );
-            thread::sleep(Duration::from_millis(10));
+            config.wait_frame();
         }
 
         let delta = bce_sigmoid_delta(&activation_output, &actual)?;
@@ -150,3 +128,18 @@ pub fn demonstrate_logic(rng: &mut dyn Rng, gate: AnimatedGate) -> Result<(), Te
     }
     Ok(())
 }
+
+/// "Predict 1" and "Predict 0" regions on the plotted grid (every 2 units over 0..=20),
+/// classified by the sigmoid of the layer's actual output at each point
+pub fn gate_regions(linear_layer: &Linear) -> Result<Vec<Trace>, TensorError> {
+    decision_grid(
+        |x, y| {
+            let point = Tensor::new(vec![x, y, 1.0], vec![1, 3])?;
+            Ok(linear_layer.forward_infer(&point)?.sigmoid()?.data()[0])
+        },
+        (0.0, 20.0),
+        (0.0, 20.0),
+        10,
+        10,
+    )
+}
@@ -1,10 +1,21 @@
-use std::{thread, time::Duration};
+use crate::{Layer, Rng, activation::{Activation, ActivationType}, examples::AnimationConfig, image_utils::{Palette, PlotColor, Trace, render_plot_palette}, linear::Linear, loss::bce_sigmoid_delta, tensor::{Tensor, TensorError}};
 
-use crate::{Layer, Rng, activation::{Activation, ActivationType}, image_utils::{PlotColor, Trace, render_plot}, linear::Linear, loss::bce_sigmoid_delta, tensor::{Tensor, TensorError}};
+/// Playback used by the NOT gate animation
+pub fn animation_config() -> AnimationConfig {
+    AnimationConfig {
+        epochs: 500,
+        render_every: 15,
+        frame_delay_ms: 40,
+        width: 70,
+        height: 25,
+        frame_dir: None,
+        palette: Palette::Standard,
+    }
+}
 
-pub fn not_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
+pub fn not_neural_network(rng: &mut dyn Rng, config: &AnimationConfig) -> Result<(), TensorError> {
     // 2 inputs: (X-coordinate and Bias) -> 1 output
-    let mut linear_layer = Linear::new(2, 1, rng);
+    let mut linear_layer = Linear::new_uniform(2, 1, rng);
     
     // Initial weights: a negative weight for w1 will help the NOT logic
     linear_layer.set_weight(Tensor::new(vec![-1.0, 5.0], vec![2, 1])?);
@@ -28,11 +39,11 @@ pub fn not_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
 
     print!("\x1b[?25l"); // Hide cursor
 
-    for epoch in 0..500 {
+    for epoch in 0..config.epochs {
         let linear_output = linear_layer.forward(&input)?;
         let activation_output = activation_layer.forward(&linear_output)?;
 
-        if epoch % 15 == 0 {
+        if config.should_render(epoch) {
             print!("\x1b[2J\x1b[1;1H"); // Clear screen
             let mut traces = Vec::new();
             let w = linear_layer.weight().data();
@@ -64,7 +75,7 @@ pub fn not_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
 
             // Boundary Line: Since it's 1D, the boundary is a vertical line where w1*x + b = 0
             let boundary_x = -b / w1;
-            if boundary_x >= 0.0 && boundary_x <= 20.0 {
+            if (0.0..=20.0).contains(&boundary_x) {
                 traces.push(Trace {
                     name: format!("Boundary (x={:.1})", boundary_x),
                     x: vec![boundary_x, boundary_x],
@@ -90,8 +101,8 @@ pub fn not_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
                 });
             }
 
-            render_plot(&traces, 70, 25, bounds, format!("NOT Gate (Epoch {})", epoch));
-            thread::sleep(Duration::from_millis(40));
+            render_plot_palette(&traces, config.width, config.height, bounds, format!("NOT Gate (Epoch {})", epoch), config.palette);
+            config.wait_frame();
         }
 
         let delta = bce_sigmoid_delta(&activation_output, &actual)?;
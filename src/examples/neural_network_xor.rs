@@ -2,12 +2,13 @@ use crate::{
     Rng,
     activation::{Activation, ActivationType},
     linear::Linear,
-    loss::bce_sigmoid_delta,
+    loss::{bce_sigmoid_delta, mse_loss},
     neural_network::NetworkBuilder,
     tensor::{Tensor, TensorError},
 };
 
-pub fn xor_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
+/// Returns the final prediction and the final MSE loss against the XOR truth table
+pub fn xor_neural_network(rng: &mut dyn Rng) -> Result<(Tensor, f32), TensorError> {
     let mut nn = NetworkBuilder::new()
         .add_layer(Box::new(Linear::new(3, 12, rng)))
         .add_layer(Box::new(Activation::new(ActivationType::ReLU)))
@@ -34,5 +35,7 @@ pub fn xor_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
 
     println!("Model Output after training:\n{}", model_output);
 
-    Ok(())
+    let loss = mse_loss(&model_output, &actual)?.data()[0];
+
+    Ok((model_output, loss))
 }
@@ -1,16 +1,24 @@
 use crate::{
     Layer, Rng,
     activation::{Activation, ActivationType},
-    image_utils::{PlotColor, Trace, render_dual_plots, render_plot},
+    examples::{AnimationConfig, decision_grid},
+    image_utils::{Palette, PlotColor, Trace, format_signed, render_dual_plots_palette},
     linear::Linear,
     loss::bce_sigmoid_delta,
     tensor::{Tensor, TensorError},
 };
-use std::time::Duration;
-use std::{fmt::format, thread};
 
-pub fn xor_neural_network(rng: &mut dyn Rng, xnor: bool) -> Result<(), TensorError> {
-    let mut l1 = Linear::new(3, 3, rng);
+/// Playback used by the XOR/XNOR animation
+pub fn animation_config() -> AnimationConfig {
+    AnimationConfig::default()
+}
+
+pub fn xor_neural_network(
+    rng: &mut dyn Rng,
+    xnor: bool,
+    config: &AnimationConfig,
+) -> Result<(), TensorError> {
+    let mut l1 = Linear::new_uniform(3, 3, rng);
     let mut a1 = Activation::new(ActivationType::Sigmoid);
 
     let weight_init = match xnor {
@@ -37,7 +45,7 @@ pub fn xor_neural_network(rng: &mut dyn Rng, xnor: bool) -> Result<(), TensorErr
     let learning_rate = 0.5;
     let bounds = Some((0.0, 1.0, 0.0, 1.0));
 
-    for epoch in 0..25000 {
+    for epoch in 0..config.epochs {
         let z1 = l1.forward(&input)?;
         let h1 = a1.forward(&z1)?;
         let z2 = l2.forward(&h1)?;
@@ -48,33 +56,23 @@ pub fn xor_neural_network(rng: &mut dyn Rng, xnor: bool) -> Result<(), TensorErr
         let d_z1 = a1.backward(&d_h1, learning_rate)?;
         let _ = l1.backward(&d_z1, learning_rate)?;
 
-        if epoch % 100 == 0 {
+        if config.should_render(epoch) {
             let mut traces = Vec::new();
-            let mut cyan_x = Vec::new();
-            let mut cyan_y = Vec::new();
-            let mut magenta_x = Vec::new();
-            let mut magenta_y = Vec::new();
-
-            for gx in 0..=20 {
-                for gy in 0..=20 {
-                    let x = gx as f32 / 20.0;
-                    let y = gy as f32 / 20.0;
 
+            let regions = decision_grid(
+                |x, y| {
                     let test_in = Tensor::new(vec![x, y, 1.0], vec![1, 3])?;
                     let p_z1 = l1.forward(&test_in)?;
                     let p_h1 = a1.forward(&p_z1)?;
                     let p_z2 = l2.forward(&p_h1)?;
                     let p_out = a2.forward(&p_z2)?;
-
-                    if p_out.data()[0] > 0.5 {
-                        cyan_x.push(x);
-                        cyan_y.push(y);
-                    } else {
-                        magenta_x.push(x);
-                        magenta_y.push(y);
-                    }
-                }
-            }
+                    Ok(p_out.data()[0])
+                },
+                (0.0, 1.0),
+                (0.0, 1.0),
+                20,
+                20,
+            )?;
 
             traces.push(Trace {
                 name: format!("Epoch {}", epoch),
@@ -84,22 +82,7 @@ pub fn xor_neural_network(rng: &mut dyn Rng, xnor: bool) -> Result<(), TensorErr
                 is_line: false,
                 hide_axes: false,
             });
-            traces.push(Trace {
-                name: "Predict 1".into(),
-                x: cyan_x,
-                y: cyan_y,
-                color: PlotColor::Cyan,
-                is_line: false,
-                hide_axes: false,
-            });
-            traces.push(Trace {
-                name: "Predict 0".into(),
-                x: magenta_x,
-                y: magenta_y,
-                color: PlotColor::Magenta,
-                is_line: false,
-                hide_axes: false,
-            });
+            traces.extend(regions);
 
             let x_pts = [0.0, 0.0, 1.0, 1.0];
             let y_pts = [0.0, 1.0, 0.0, 1.0];
@@ -121,23 +104,25 @@ pub fn xor_neural_network(rng: &mut dyn Rng, xnor: bool) -> Result<(), TensorErr
 
             let topology_traces = visualize_topology(l1.weight(), l2.weight());
 
-            render_dual_plots(
+            render_dual_plots_palette(
                 &topology_traces,
                 &traces,
-                70,
-                25,
+                config.width,
+                config.height,
                 bounds,
                 format!(
                     "{} Training - Epoch {}",
                     if xnor { "XNOR" } else { "XOR" },
                     epoch
                 ),
+                config.palette,
             );
 
-            let weight_display = format_weights_side_by_side(l1.weight(), l2.weight());
+            let weight_display =
+                format_weights_side_by_side(l1.weight(), l2.weight(), config.palette);
             println!("{}", weight_display);
 
-            thread::sleep(Duration::from_millis(10));
+            config.wait_frame();
         }
     }
 
@@ -236,7 +221,11 @@ pub fn visualize_topology(l1_weights: &Tensor, l2_weights: &Tensor) -> Vec<Trace
 }
 
 /// Formats two tensors (matrices) side-by-side as strings for the terminal
-pub fn format_weights_side_by_side(l1_weights: &Tensor, l2_weights: &Tensor) -> String {
+pub fn format_weights_side_by_side(
+    l1_weights: &Tensor,
+    l2_weights: &Tensor,
+    palette: Palette,
+) -> String {
     let mut output = String::new();
 
     // Get raw data and shapes
@@ -257,9 +246,10 @@ pub fn format_weights_side_by_side(l1_weights: &Tensor, l2_weights: &Tensor) ->
             row_str.push_str("[ ");
             for c in 0..s1[1] {
                 let val = w1[r * s1[1] + c];
-                row_str.push_str(&format!("{:>7.3} ", val));
+                row_str.push_str(&format_signed(val, 7, 3, palette));
+                row_str.push(' ');
             }
-            row_str.push_str("]");
+            row_str.push(']');
         } else {
             row_str.push_str(&" ".repeat(s1[1] * 8 + 3));
         }
@@ -272,9 +262,10 @@ pub fn format_weights_side_by_side(l1_weights: &Tensor, l2_weights: &Tensor) ->
             row_str.push_str("[ ");
             for c in 0..s2[1] {
                 let val = w2[r * s2[1] + c];
-                row_str.push_str(&format!("{:>7.3} ", val));
+                row_str.push_str(&format_signed(val, 7, 3, palette));
+                row_str.push(' ');
             }
-            row_str.push_str("]");
+            row_str.push(']');
         }
 
         output.push_str(&row_str);
@@ -1,12 +1,27 @@
 use crate::{
     Layer, Rng,
     activation::{Activation, ActivationType},
-    image_utils::{PlotColor, Trace, render_dual_plots},
+    examples::{AnimationConfig, confidence_grid, decision_frame},
+    image_utils::{
+        Palette, PlotColor, Trace, format_signed, render_dual_plots_palette, save_frame_pbm,
+    },
     linear::Linear,
     loss::bce_sigmoid_delta,
-    tensor::{Tensor, TensorError},
+    tensor::Tensor,
 };
-use std::{thread, time::Duration};
+
+/// Playback used by the two moons animation
+pub fn animation_config() -> AnimationConfig {
+    AnimationConfig {
+        epochs: 100_000,
+        render_every: 500,
+        frame_delay_ms: 50,
+        width: 100,
+        height: 30,
+        frame_dir: None,
+        palette: Palette::Standard,
+    }
+}
 
 /// Generates the Two Moons dataset
 pub fn generate_two_moons(samples: usize) -> (Tensor, Tensor) {
@@ -38,7 +53,13 @@ pub fn generate_two_moons(samples: usize) -> (Tensor, Tensor) {
     (x_tensor, y_tensor)
 }
 
-pub fn two_moons_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
+/// Pixel size of the frames saved when `frame_dir` is set
+const FRAME_SIZE: (usize, usize) = (80, 50);
+
+pub fn two_moons_neural_network(
+    rng: &mut dyn Rng,
+    config: &AnimationConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
     // 1. Setup Architecture: 3 Inputs (x, y, bias) -> 9 Hidden -> 1 Output
     let mut l1 = Linear::new(3, 9, rng);
     let mut a1 = Activation::new(ActivationType::Sigmoid);
@@ -52,7 +73,7 @@ pub fn two_moons_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
     // Bounds adjusted for Two Moons coordinates
     let bounds = Some((-1.5, 2.5, -1.0, 1.5));
 
-    for epoch in 0..100_000 {
+    for epoch in 0..config.epochs {
         // Forward & Backward pass
         let z1 = l1.forward(&input)?;
         let h1 = a1.forward(&z1)?;
@@ -64,46 +85,19 @@ pub fn two_moons_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
         let d_z1 = a1.backward(&d_h1, learning_rate)?;
         let _ = l1.backward(&d_z1, learning_rate)?;
 
-        if epoch % 500 == 0 {
-            let mut traces = Vec::new();
-            let (mut cx, mut cy, mut mx, mut my) = (vec![], vec![], vec![], vec![]);
-
+        if config.should_render(epoch) {
             // 3. Generate Decision Boundary "Heatmap"
-            for gx in 0..=30 {
-                for gy in 0..=20 {
-                    let x = -1.5 + (gx as f32 / 30.0) * 4.0;
-                    let y = -1.0 + (gy as f32 / 20.0) * 2.5;
-
+            let mut traces = confidence_grid(
+                |x, y| {
                     let test_in = Tensor::new(vec![x, y, 1.0], vec![1, 3])?;
                     let p_out = a2.forward(&l2.forward(&a1.forward(&l1.forward(&test_in)?)?)?)?;
-
-                    if p_out.data()[0] > 0.5 {
-                        cx.push(x);
-                        cy.push(y);
-                    } else {
-                        mx.push(x);
-                        my.push(y);
-                    }
-                }
-            }
-
-            // Add Heatmap Traces
-            traces.push(Trace {
-                name: "Class 1 Area".into(),
-                x: cx,
-                y: cy,
-                color: PlotColor::Cyan,
-                is_line: false,
-                hide_axes: false,
-            });
-            traces.push(Trace {
-                name: "Class 0 Area".into(),
-                x: mx,
-                y: my,
-                color: PlotColor::Magenta,
-                is_line: false,
-                hide_axes: false,
-            });
+                    Ok(p_out.data()[0])
+                },
+                (-1.5, 2.5),
+                (-1.0, 1.5),
+                30,
+                20,
+            )?;
 
             // 4. Add Actual Data Points
             for i in 0..actual.data().len() {
@@ -122,19 +116,37 @@ pub fn two_moons_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
                 });
             }
 
-            render_dual_plots(
+            render_dual_plots_palette(
                 &visualize_topology(l1.weight(), l2.weight(), -1.0, 1.5), // Note: Update visualize_topology for new layer sizes!
                 &traces,
-                100,
-                30,
+                config.width,
+                config.height,
                 bounds,
                 format!("Two Moons Training - Epoch {}", epoch),
+                config.palette,
             );
 
-            let weight_display = format_weights_side_by_side(l1.weight(), l2.weight());
+            let weight_display =
+                format_weights_side_by_side(l1.weight(), l2.weight(), config.palette);
             println!("{}", weight_display);
 
-            thread::sleep(Duration::from_millis(50));
+            if let Some(dir) = &config.frame_dir {
+                let frame = decision_frame(
+                    |x, y| {
+                        let test_in = Tensor::new(vec![x, y, 1.0], vec![1, 3])?;
+                        let z1 = l1.forward_infer(&test_in)?;
+                        let h1 = a1.forward_infer(&z1)?;
+                        let z2 = l2.forward_infer(&h1)?;
+                        let p_out = a2.forward_infer(&z2)?;
+                        Ok(p_out.data()[0])
+                    },
+                    (-1.5, 2.5, -1.0, 1.5),
+                    FRAME_SIZE,
+                )?;
+                save_frame_pbm(dir, epoch / config.render_every.max(1), FRAME_SIZE.0, FRAME_SIZE.1, &frame)?;
+            }
+
+            config.wait_frame();
         }
     }
     Ok(())
@@ -187,10 +199,8 @@ pub fn visualize_topology(
     }
 
     // Drawing logic for lines (Input -> Hidden)
-    let w1 = l1_weights.data();
-    for i in 0..input_count {
-        for j in 0..hidden_count {
-            let weight = w1[i * hidden_count + j];
+    for (i, w1_row) in l1_weights.rows().enumerate() {
+        for (j, &weight) in w1_row.iter().enumerate() {
             traces.push(Trace {
                 name: "".into(),
                 x: vec![node_coords[0][i].0, node_coords[1][j].0],
@@ -203,10 +213,8 @@ pub fn visualize_topology(
     }
 
     // Drawing logic for lines (Hidden -> Output)
-    let w2 = l2_weights.data();
-    for i in 0..hidden_count {
-        for j in 0..output_count {
-            let weight = w2[i * output_count + j];
+    for (i, w2_row) in l2_weights.rows().enumerate() {
+        for (j, &weight) in w2_row.iter().enumerate() {
             traces.push(Trace {
                 name: "".into(),
                 x: vec![node_coords[1][i].0, node_coords[2][j].0],
@@ -240,7 +248,11 @@ pub fn visualize_topology(
 }
 
 
-pub fn format_weights_side_by_side(l1_weights: &Tensor, l2_weights: &Tensor) -> String {
+pub fn format_weights_side_by_side(
+    l1_weights: &Tensor,
+    l2_weights: &Tensor,
+    palette: Palette,
+) -> String {
     let mut output = String::new();
 
     let w1 = l1_weights.data();
@@ -250,11 +262,6 @@ pub fn format_weights_side_by_side(l1_weights: &Tensor, l2_weights: &Tensor) ->
 
     let max_rows = s1[0].max(s2[0]);
 
-    // Import or use the ANSI codes from your PlotColor enum
-    let green = "\x1b[32m";
-    let red = "\x1b[31m";
-    let reset = "\x1b[0m";
-
     output.push_str("\n    L1 Weights (Input -> Hidden)         L2 Weights (Hidden -> Output)\n");
     output.push_str("    ----------------------------         -----------------------------\n");
 
@@ -266,10 +273,10 @@ pub fn format_weights_side_by_side(l1_weights: &Tensor, l2_weights: &Tensor) ->
             row_str.push_str("[ ");
             for c in 0..s1[1] {
                 let val = w1[r * s1[1] + c];
-                let color = if val >= 0.0 { green } else { red };
-                row_str.push_str(&format!("{}{:>7.3}{} ", color, val, reset));
+                row_str.push_str(&format_signed(val, 7, 3, palette));
+                row_str.push(' ');
             }
-            row_str.push_str("]");
+            row_str.push(']');
         } else {
             // Adjust spacing for ANSI codes (which don't take up visual space)
             row_str.push_str(&" ".repeat(s1[1] * 8 + 3));
@@ -282,10 +289,10 @@ pub fn format_weights_side_by_side(l1_weights: &Tensor, l2_weights: &Tensor) ->
             row_str.push_str("[ ");
             for c in 0..s2[1] {
                 let val = w2[r * s2[1] + c];
-                let color = if val >= 0.0 { green } else { red };
-                row_str.push_str(&format!("{}{:>7.3}{} ", color, val, reset));
+                row_str.push_str(&format_signed(val, 7, 3, palette));
+                row_str.push(' ');
             }
-            row_str.push_str("]");
+            row_str.push(']');
         }
 
         output.push_str(&row_str);
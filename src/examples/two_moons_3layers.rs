@@ -1,12 +1,25 @@
 use crate::{
     Layer, Rng,
     activation::{Activation, ActivationType},
-    image_utils::{PlotColor, Trace, render_dual_plots},
+    examples::{AnimationConfig, confidence_grid},
+    image_utils::{Palette, PlotColor, Trace, format_signed, render_dual_plots_palette},
     linear::Linear,
     loss::bce_sigmoid_delta,
     tensor::{Tensor, TensorError},
 };
-use std::{thread, time::Duration};
+
+/// Playback used by the two moons animation
+pub fn animation_config() -> AnimationConfig {
+    AnimationConfig {
+        epochs: 100_000,
+        render_every: 500,
+        frame_delay_ms: 50,
+        width: 100,
+        height: 30,
+        frame_dir: None,
+        palette: Palette::Standard,
+    }
+}
 
 /// Generates the Two Moons dataset
 pub fn generate_two_moons(samples: usize) -> (Tensor, Tensor) {
@@ -38,7 +51,10 @@ pub fn generate_two_moons(samples: usize) -> (Tensor, Tensor) {
     (x_tensor, y_tensor)
 }
 
-pub fn two_moons_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
+pub fn two_moons_neural_network(
+    rng: &mut dyn Rng,
+    config: &AnimationConfig,
+) -> Result<(), TensorError> {
     // 1. Architecture: 3 -> 9 -> 6 -> 1
     let mut l1 = Linear::new(3, 9, rng);
     let mut a1 = Activation::new(ActivationType::Sigmoid);
@@ -51,7 +67,7 @@ pub fn two_moons_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
     let learning_rate = 0.08;
     let bounds = Some((-1.5, 2.5, -1.0, 1.5));
 
-    for epoch in 0..100_000 {
+    for epoch in 0..config.epochs {
         // Forward Pass
         let h1 = a1.forward(&l1.forward(&input)?)?;
         let h2 = a2.forward(&l2.forward(&h1)?)?;
@@ -65,26 +81,16 @@ pub fn two_moons_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
         let d_z1 = a1.backward(&d_h1, learning_rate)?;
         let _ = l1.backward(&d_z1, learning_rate)?;
 
-        if epoch % 500 == 0 {
-            let mut traces = Vec::new();
-            let (mut cx, mut cy, mut mx, mut my) = (vec![], vec![], vec![], vec![]);
-
+        if config.should_render(epoch) {
             // 2. Heatmap Generation
-            for gx in 0..=30 {
-                for gy in 0..=20 {
-                    let x = -1.5 + (gx as f32 / 30.0) * 4.0;
-                    let y = -1.0 + (gy as f32 / 20.0) * 2.5;
+            let mut traces = confidence_grid(
+                |x, y| {
                     let test_in = Tensor::new(vec![x, y, 1.0], vec![1, 3])?;
                     let p = a3.forward(&l3.forward(&a2.forward(&l2.forward(&a1.forward(&l1.forward(&test_in)?)?)?)?)?)?;
-
-                    if p.data()[0] > 0.5 { cx.push(x); cy.push(y); } 
-                    else { mx.push(x); my.push(y); }
-                }
-            }
-
-            // Heatmap Traces
-            traces.push(Trace { name: "Class 1 Area".into(), x: cx, y: cy, color: PlotColor::Cyan, is_line: false, hide_axes: false });
-            traces.push(Trace { name: "Class 0 Area".into(), x: mx, y: my, color: PlotColor::Magenta, is_line: false, hide_axes: false });
+                    Ok(p.data()[0])
+                },
+                (-1.5, 2.5), (-1.0, 1.5), 30, 20,
+            )?;
 
             // 3. Data Points
             for i in 0..actual.data().len() {
@@ -96,14 +102,15 @@ pub fn two_moons_neural_network(rng: &mut dyn Rng) -> Result<(), TensorError> {
             }
 
             // Render with dynamic heights
-            render_dual_plots(
+            render_dual_plots_palette(
                 &visualize_topology_dynamic(l1.weight(), l2.weight(), l3.weight(), -1.0, 1.5),
-                &traces, 100, 30, bounds,
+                &traces, config.width, config.height, bounds,
                 format!("Two Moons Training - Epoch {}", epoch),
+                config.palette,
             );
 
-            println!("{}", format_3_layer_weights(l1.weight(), l2.weight(), l3.weight()));
-            thread::sleep(Duration::from_millis(50));
+            println!("{}", format_3_layer_weights(l1.weight(), l2.weight(), l3.weight(), config.palette));
+            config.wait_frame();
         }
     }
     Ok(())
@@ -177,10 +184,9 @@ pub fn visualize_topology_dynamic(l1: &Tensor, l2: &Tensor, l3: &Tensor, y_min:
     traces
 }
 
-pub fn format_3_layer_weights(l1: &Tensor, l2: &Tensor, l3: &Tensor) -> String {
+pub fn format_3_layer_weights(l1: &Tensor, l2: &Tensor, l3: &Tensor, palette: Palette) -> String {
     let mut out = String::from("\n      L1 (3->9)                                                               L2 (9->6)                                  L3 (6->1)\n");
     let max_rows = l1.shape()[0].max(l2.shape()[0]).max(l3.shape()[0]);
-    let (g, r, res) = ("\x1b[32m", "\x1b[31m", "\x1b[0m");
 
     for row_idx in 0..max_rows {
         let mut line = String::from("  ");
@@ -189,7 +195,8 @@ pub fn format_3_layer_weights(l1: &Tensor, l2: &Tensor, l3: &Tensor) -> String {
                 line.push('[');
                 for col_idx in 0..w.shape()[1] {
                     let val = w.data()[row_idx * w.shape()[1] + col_idx];
-                    line.push_str(&format!("{}{:>6.2}{} ", if val >= 0.0 {g} else {r}, val, res));
+                    line.push_str(&format_signed(val, 6, 2, palette));
+                    line.push(' ');
                 }
                 line.push(']');
             } else {
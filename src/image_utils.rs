@@ -1,78 +1,533 @@
 use std::{
+    error::Error,
     f32,
     fs::File,
-    io::{BufWriter, Write},
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
 };
 
-pub fn read_pbm_for_nn(path: &str) -> (usize, usize, Vec<f32>, Vec<f32>) {
-    let content = std::fs::read_to_string(path).expect("Read failed");
-    let mut tokens = content.split_whitespace();
+/// Ways reading a PBM file can fail
+#[derive(Debug)]
+pub enum ImageError {
+    Io(std::io::Error),
+    /// The file does not start with `P1` or `P4`
+    BadMagic,
+    /// Width or height is missing a number, zero, or too large
+    BadDimensions,
+    /// The file ends before the header or all pixels were read
+    UnexpectedEof,
+    /// A plain PBM pixel is not a number
+    BadPixel,
+}
+
+impl Error for ImageError {}
+
+impl std::fmt::Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageError::Io(err) => write!(f, "Could not read the image: {}", err),
+            ImageError::BadMagic => write!(f, "Not a PBM (P1 or P4) file."),
+            ImageError::BadDimensions => write!(f, "PBM width or height is invalid."),
+            ImageError::UnexpectedEof => write!(f, "PBM file ends before all pixels were read."),
+            ImageError::BadPixel => write!(f, "PBM pixel value is not a number."),
+        }
+    }
+}
+
+impl From<std::io::Error> for ImageError {
+    fn from(err: std::io::Error) -> Self {
+        ImageError::Io(err)
+    }
+}
+
+/// Reads a plain (P1) or binary (P4) PBM file into its width, height and row-major 0/1 pixels
+pub fn read_pbm(path: &str) -> Result<(usize, usize, Vec<f32>), ImageError> {
+    let bytes = std::fs::read(path)?;
+    if bytes.starts_with(b"P4") {
+        return parse_pbm_binary(&bytes);
+    }
+    if !bytes.starts_with(b"P1") {
+        return Err(ImageError::BadMagic);
+    }
+    // Invalid UTF-8 turns into replacement characters, which then fail as pixels
+    let content = String::from_utf8_lossy(&bytes);
+
+    // Comments run until the end of the line, so drop them before tokenizing
+    let mut tokens = content
+        .lines()
+        .map(|l| l.split('#').next().unwrap_or(""))
+        .flat_map(|l| l.split_whitespace())
+        .skip(1);
+
+    let w = parse_dimension(tokens.next())?;
+    let h = parse_dimension(tokens.next())?;
+    let len = w.checked_mul(h).ok_or(ImageError::BadDimensions)?;
+
+    let mut pixels = Vec::with_capacity(len);
+    for token in tokens {
+        if pixels.len() >= len {
+            break;
+        }
+        push_pbm_pixels(token.as_bytes(), &mut pixels)?;
+    }
+    pixels.truncate(len);
+
+    if pixels.len() != len {
+        return Err(ImageError::UnexpectedEof);
+    }
+
+    Ok((w, h, pixels))
+}
+
+/// Appends the pixels of one P1 raster token. Some tools write rows as packed digits
+/// without spaces (`101`), so a run of 0/1 characters is one pixel per character;
+/// anything else must be a single number.
+fn push_pbm_pixels(token: &[u8], pixels: &mut Vec<f32>) -> Result<(), ImageError> {
+    if token.len() > 1 && token.iter().all(|b| matches!(b, b'0' | b'1')) {
+        pixels.extend(token.iter().map(|&b| (b - b'0') as f32));
+        return Ok(());
+    }
+
+    let pixel = std::str::from_utf8(token)
+        .ok()
+        .and_then(|t| t.parse::<f32>().ok())
+        .ok_or(ImageError::BadPixel)?;
+    pixels.push(pixel);
+    Ok(())
+}
+
+/// A header width or height: missing means the file was cut short
+fn parse_dimension(token: Option<&str>) -> Result<usize, ImageError> {
+    match token.ok_or(ImageError::UnexpectedEof)?.parse::<usize>() {
+        Ok(d) if d > 0 => Ok(d),
+        _ => Err(ImageError::BadDimensions),
+    }
+}
+
+/// P4 stores the same header as P1, then a single whitespace byte and the pixels
+/// packed 8 per byte, most significant bit first, with every row padded to a whole byte
+fn parse_pbm_binary(bytes: &[u8]) -> Result<(usize, usize, Vec<f32>), ImageError> {
+    let mut pos = 2;
+    let mut dims = [0usize; 2];
+    for dim in dims.iter_mut() {
+        // Skip whitespace and comments up to the next number
+        while pos < bytes.len() && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b'#') {
+            if bytes[pos] == b'#' {
+                while pos < bytes.len() && bytes[pos] != b'\n' {
+                    pos += 1;
+                }
+            } else {
+                pos += 1;
+            }
+        }
+
+        let start = pos;
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        if start == bytes.len() {
+            return Err(ImageError::UnexpectedEof);
+        }
+        // Only ASCII digits were consumed, so the slice is valid UTF-8
+        let digits = std::str::from_utf8(&bytes[start..pos]).unwrap_or_default();
+        *dim = parse_dimension(Some(digits))?;
+    }
+    let [w, h] = dims;
 
-    assert_eq!(tokens.next().unwrap(), "P1");
-    let w: usize = tokens.next().unwrap().parse().unwrap();
-    let h: usize = tokens.next().unwrap().parse().unwrap();
+    // Exactly one whitespace byte separates the header from the raster
+    let row_bytes = w.div_ceil(8);
+    let raster_len = row_bytes.checked_mul(h).ok_or(ImageError::BadDimensions)?;
+    let raster = bytes
+        .get(pos + 1..pos + 1 + raster_len)
+        .ok_or(ImageError::UnexpectedEof)?;
+
+    let pixels = raster
+        .chunks(row_bytes)
+        .flat_map(|row| (0..w).map(move |c| ((row[c / 8] >> (7 - c % 8)) & 1) as f32))
+        .collect();
+
+    Ok((w, h, pixels))
+}
+
+pub fn read_pbm_for_nn(path: &str) -> Result<(usize, usize, Vec<f32>, Vec<f32>), ImageError> {
+    let (w, h, y_values) = read_pbm(path)?;
 
     let mut x_coords = Vec::with_capacity(w * h * 2);
-    let mut y_values = Vec::with_capacity(w * h);
 
     for i in 0..(w * h) {
         // Input: [Row, Col]
         x_coords.push((i / w) as f32);
         x_coords.push((i % w) as f32);
-        // Target: [Pixel]
-        y_values.push(tokens.next().unwrap().parse().unwrap());
     }
 
-    (w, h, x_coords, y_values)
+    // Target: [Pixel]
+    Ok((w, h, x_coords, y_values))
+}
+
+/// Same result as `read_pbm_for_nn`, but the file is scanned through a `BufReader` one token
+/// (or, for P4, one packed row) at a time, so memory only grows with the returned vectors
+/// and never holds the whole file
+pub fn read_pbm_for_nn_streaming(
+    path: &str,
+) -> Result<(usize, usize, Vec<f32>, Vec<f32>), ImageError> {
+    let mut scanner = PbmScanner {
+        reader: BufReader::new(File::open(path)?),
+    };
+
+    let magic = scanner.next_token()?.unwrap_or_default();
+    let binary = match magic.get(..2) {
+        Some(b"P1") => false,
+        Some(b"P4") => true,
+        _ => return Err(ImageError::BadMagic),
+    };
+
+    let w = scanner.next_dimension()?;
+    let h = scanner.next_dimension()?;
+    let len = w.checked_mul(h).ok_or(ImageError::BadDimensions)?;
+
+    let mut x_coords = Vec::with_capacity(len * 2);
+    let mut y_values = Vec::with_capacity(len);
+    for i in 0..len {
+        // Input: [Row, Col]
+        x_coords.push((i / w) as f32);
+        x_coords.push((i % w) as f32);
+    }
+
+    if binary {
+        // Exactly one whitespace byte separates the header from the raster
+        scanner.next_byte()?.ok_or(ImageError::UnexpectedEof)?;
+
+        let mut row = vec![0u8; w.div_ceil(8)];
+        for _ in 0..h {
+            scanner
+                .reader
+                .read_exact(&mut row)
+                .map_err(|err| match err.kind() {
+                    std::io::ErrorKind::UnexpectedEof => ImageError::UnexpectedEof,
+                    _ => ImageError::Io(err),
+                })?;
+            y_values.extend((0..w).map(|c| ((row[c / 8] >> (7 - c % 8)) & 1) as f32));
+        }
+    } else {
+        while y_values.len() < len {
+            let token = scanner.next_token()?.ok_or(ImageError::UnexpectedEof)?;
+            push_pbm_pixels(&token, &mut y_values)?;
+        }
+        y_values.truncate(len);
+    }
+
+    // Target: [Pixel]
+    Ok((w, h, x_coords, y_values))
+}
+
+/// Pulls whitespace-separated PBM header and P1 pixel tokens out of a reader,
+/// dropping `#` comments up to the end of their line
+struct PbmScanner<R: BufRead> {
+    reader: R,
+}
+
+impl<R: BufRead> PbmScanner<R> {
+    fn peek_byte(&mut self) -> std::io::Result<Option<u8>> {
+        Ok(self.reader.fill_buf()?.first().copied())
+    }
+
+    fn next_byte(&mut self) -> std::io::Result<Option<u8>> {
+        let byte = self.peek_byte()?;
+        if byte.is_some() {
+            self.reader.consume(1);
+        }
+        Ok(byte)
+    }
+
+    /// The next token, or `None` at the end of the file. The byte that ends the token is
+    /// left unread, which P4 relies on to find the start of the raster.
+    fn next_token(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+        loop {
+            match self.peek_byte()? {
+                Some(b'#') => while !matches!(self.next_byte()?, Some(b'\n') | None) {},
+                Some(b) if b.is_ascii_whitespace() => self.reader.consume(1),
+                Some(_) => break,
+                None => return Ok(None),
+            }
+        }
+
+        let mut token = Vec::new();
+        while let Some(b) = self.peek_byte()? {
+            if b.is_ascii_whitespace() || b == b'#' {
+                break;
+            }
+            token.push(b);
+            self.reader.consume(1);
+        }
+        Ok(Some(token))
+    }
+
+    fn next_dimension(&mut self) -> Result<usize, ImageError> {
+        let token = self.next_token()?;
+        parse_dimension(
+            token
+                .as_deref()
+                .map(|t| std::str::from_utf8(t).unwrap_or("")),
+        )
+    }
+}
+
+/// Cut-off used when saving network output as a PBM file
+pub const PBM_THRESHOLD: f32 = 0.5;
+/// Stricter cut-off used by `render_image`, so only confident pixels show up in the terminal
+pub const RENDER_THRESHOLD: f32 = 0.8;
+
+/// The one binarization rule shared by rendering and saving. Values strictly above
+/// `threshold` are on and values strictly below are off. A value exactly at the
+/// threshold is on when `round_half_up` is set, and off otherwise.
+pub fn pixel_on(value: f32, threshold: f32, round_half_up: bool) -> bool {
+    if round_half_up {
+        value >= threshold
+    } else {
+        value > threshold
+    }
 }
 
 pub fn render_image(w: usize, h: usize, data: &[f32]) {
-    let threshold = 0.8;
-
-    for y in (0..h).step_by(4) {
-        let mut row = String::new();
-        for x in (0..w).step_by(2) {
-            let mut byte = 0u8;
-            let dots = [
-                (0, 0, 0x01),
-                (0, 1, 0x02),
-                (0, 2, 0x04),
-                (1, 0, 0x08),
-                (1, 1, 0x10),
-                (1, 2, 0x20),
-                (0, 3, 0x40),
-                (1, 3, 0x80),
-            ];
-
-            for (dx, dy, mask) in dots {
-                let (px, py) = (x + dx, y + dy);
-                if px < w && py < h {
-                    if data[py * w + px] >= threshold {
-                        byte |= mask;
+    render_image_with_threshold(w, h, data, RENDER_THRESHOLD)
+}
+
+/// Same as `render_image`, with an explicit cut-off for lit dots
+pub fn render_image_with_threshold(w: usize, h: usize, data: &[f32], threshold: f32) {
+    render_image_styled(w, h, data, RenderStyle::Braille, threshold)
+}
+
+/// Characters used to draw an image in the terminal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderStyle {
+    /// 2x4 pixels per character, needs a font with the Braille block
+    Braille,
+    /// 1x2 pixels per character with the widely supported half and full blocks
+    HalfBlock,
+}
+
+pub fn render_image_styled(w: usize, h: usize, data: &[f32], style: RenderStyle, threshold: f32) {
+    for row in render_image_lines(w, h, data, style, threshold) {
+        println!("{}", row);
+    }
+}
+
+/// The text lines `render_image_styled` prints: `ceil(h / 4)` for Braille, `ceil(h / 2)` for
+/// half blocks
+pub fn render_image_lines(
+    w: usize,
+    h: usize,
+    data: &[f32],
+    style: RenderStyle,
+    threshold: f32,
+) -> Vec<String> {
+    let lit =
+        |px: usize, py: usize| px < w && py < h && pixel_on(data[py * w + px], threshold, true);
+
+    match style {
+        RenderStyle::Braille => (0..h)
+            .step_by(4)
+            .map(|y| {
+                let mut row = String::new();
+                for x in (0..w).step_by(2) {
+                    let mut byte = 0u8;
+                    let dots = [
+                        (0, 0, 0x01),
+                        (0, 1, 0x02),
+                        (0, 2, 0x04),
+                        (1, 0, 0x08),
+                        (1, 1, 0x10),
+                        (1, 2, 0x20),
+                        (0, 3, 0x40),
+                        (1, 3, 0x80),
+                    ];
+
+                    for (dx, dy, mask) in dots {
+                        if lit(x + dx, y + dy) {
+                            byte |= mask;
+                        }
                     }
+                    row.push(std::char::from_u32(0x2800 + byte as u32).unwrap());
                 }
+                row
+            })
+            .collect(),
+        RenderStyle::HalfBlock => (0..h)
+            .step_by(2)
+            .map(|y| {
+                (0..w)
+                    .map(|x| match (lit(x, y), lit(x, y + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    })
+                    .collect()
+            })
+            .collect(),
+    }
+}
+
+pub fn draw_pbm(source: &str) {
+    let (w, h, data) = read_pbm(source).expect("Read failed");
+
+    println!("Image: {} ({}x{})", source, w, h);
+    render_image(w, h, &data);
+}
+
+/// Saves with `PBM_THRESHOLD`, values exactly at the threshold become 1
+pub fn save_as_pbm(path: &str, w: usize, h: usize, data: &[f32]) -> std::io::Result<()> {
+    save_as_pbm_with_threshold(path, w, h, data, PBM_THRESHOLD, true)
+}
+
+/// Writes a binary (P4) PBM with the same thresholding as `save_as_pbm`,
+/// about an eighth of the size of the plain format
+pub fn save_as_pbm_binary(path: &str, w: usize, h: usize, data: &[f32]) -> std::io::Result<()> {
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
+
+    writeln!(writer, "P4")?;
+    writeln!(writer, "{} {}", w, h)?;
+
+    let mut packed = vec![0u8; w.div_ceil(8)];
+    for row in data.chunks(w.max(1)) {
+        packed.fill(0);
+        for (c, &pixel) in row.iter().enumerate() {
+            if pixel_on(pixel, PBM_THRESHOLD, true) {
+                packed[c / 8] |= 0x80 >> (c % 8);
             }
-            row.push(std::char::from_u32(0x2800 + byte as u32).unwrap());
         }
-        println!("{}", row);
+        writer.write_all(&packed)?;
     }
+
+    writer.flush()?;
+    Ok(())
 }
 
-pub fn draw_pbm(source: &str) {
-    let content = std::fs::read_to_string(source).expect("Read failed");
-    let mut tokens = content.split_whitespace().filter(|t| !t.starts_with('#'));
+/// Saves one animation frame as `<dir>/frame_00042.pbm` (binary PBM), creating `dir`
+/// if needed. The zero padding keeps the frames in order for tools that sort by name.
+/// Returns the path of the written file.
+pub fn save_frame_pbm(
+    dir: &str,
+    frame_idx: usize,
+    w: usize,
+    h: usize,
+    data: &[f32],
+) -> std::io::Result<String> {
+    std::fs::create_dir_all(dir)?;
+
+    let path = Path::new(dir).join(format!("frame_{:05}.pbm", frame_idx));
+    let path = path.to_string_lossy().into_owned();
+    save_as_pbm_binary(&path, w, h, data)?;
+    Ok(path)
+}
 
-    let _magic = tokens.next(); // Skip "P1"
-    let w: usize = tokens.next().unwrap().parse().unwrap();
-    let h: usize = tokens.next().unwrap().parse().unwrap();
+/// Pause between the frames of `encode_gif`, in hundredths of a second
+const GIF_FRAME_DELAY_CS: u16 = 10;
+
+/// Writes the frames as a looping, two-colour animated GIF. Pixels are thresholded like
+/// `save_as_pbm`, and "on" pixels are black, as in a PBM.
+///
+/// The pixel codes are not actually compressed: a clear code after every two pixels keeps
+/// the LZW table (and the code width) from ever growing. The file is about half a byte per
+/// pixel, which is plenty for small training frames.
+pub fn encode_gif(frames: &[Vec<f32>], w: usize, h: usize, path: &str) -> std::io::Result<()> {
+    let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidInput, msg);
+
+    let (width, height) = match (u16::try_from(w), u16::try_from(h)) {
+        (Ok(width), Ok(height)) => (width, height),
+        _ => return Err(invalid("GIF dimensions must fit in 16 bits")),
+    };
+    if frames.iter().any(|f| f.len() != w * h) {
+        return Err(invalid("Every frame must have w * h pixels"));
+    }
 
-    // Convert ASCII "0"/"1" into actual 0 and 1 integers
-    let data: Vec<f32> = tokens.map(|t| t.parse::<f32>().unwrap()).collect();
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
 
-    render_image(w, h, &data);
+    // Header, logical screen with a 2-entry global colour table: 0 = white, 1 = black
+    writer.write_all(b"GIF89a")?;
+    writer.write_all(&width.to_le_bytes())?;
+    writer.write_all(&height.to_le_bytes())?;
+    writer.write_all(&[0x80, 0, 0])?;
+    writer.write_all(&[0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00])?;
+
+    // NETSCAPE2.0 application extension: loop forever
+    writer.write_all(&[0x21, 0xFF, 0x0B])?;
+    writer.write_all(b"NETSCAPE2.0")?;
+    writer.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+    for frame in frames {
+        // Graphic control extension carrying the frame delay
+        writer.write_all(&[0x21, 0xF9, 0x04, 0x00])?;
+        writer.write_all(&GIF_FRAME_DELAY_CS.to_le_bytes())?;
+        writer.write_all(&[0x00, 0x00])?;
+
+        // Image descriptor covering the whole screen, no local colour table
+        writer.write_all(&[0x2C, 0, 0, 0, 0])?;
+        writer.write_all(&width.to_le_bytes())?;
+        writer.write_all(&height.to_le_bytes())?;
+        writer.write_all(&[0x00])?;
+
+        writer.write_all(&[GIF_MIN_CODE_SIZE])?;
+        for block in gif_pixel_codes(frame).chunks(255) {
+            writer.write_all(&[block.len() as u8])?;
+            writer.write_all(block)?;
+        }
+        writer.write_all(&[0x00])?;
+    }
+
+    writer.write_all(&[0x3B])?;
+    writer.flush()?;
+    Ok(())
 }
 
-pub fn save_as_pbm(path: &str, w: usize, h: usize, data: &[f32]) -> std::io::Result<()> {
+/// Smallest LZW code size GIF allows, enough for the 2 colours
+const GIF_MIN_CODE_SIZE: u8 = 2;
+
+/// LZW stream for one frame: 3-bit codes packed least significant bit first
+fn gif_pixel_codes(frame: &[f32]) -> Vec<u8> {
+    const CLEAR: u32 = 1 << GIF_MIN_CODE_SIZE;
+    const END: u32 = CLEAR + 1;
+    const CODE_BITS: u32 = GIF_MIN_CODE_SIZE as u32 + 1;
+
+    let mut codes = Vec::with_capacity(frame.len() * 3 / 2 + 2);
+    for pair in frame.chunks(2) {
+        codes.push(CLEAR);
+        codes.extend(
+            pair.iter()
+                .map(|&p| pixel_on(p, PBM_THRESHOLD, true) as u32),
+        );
+    }
+    codes.push(END);
+
+    let mut bytes = Vec::with_capacity(codes.len() * CODE_BITS as usize / 8 + 1);
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for code in codes {
+        acc |= code << bits;
+        bits += CODE_BITS;
+        while bits >= 8 {
+            bytes.push(acc as u8);
+            acc >>= 8;
+            bits -= 8;
+        }
+    }
+    if bits > 0 {
+        bytes.push(acc as u8);
+    }
+    bytes
+}
+
+/// Writes a plain PBM, turning each value into "0" or "1" with `pixel_on`.
+/// Every row, including the last one, ends with a newline.
+pub fn save_as_pbm_with_threshold(
+    path: &str,
+    w: usize,
+    h: usize,
+    data: &[f32],
+    threshold: f32,
+    round_half_up: bool,
+) -> std::io::Result<()> {
     let file = File::create(path)?;
     let mut writer = BufWriter::new(file);
 
@@ -81,19 +536,16 @@ pub fn save_as_pbm(path: &str, w: usize, h: usize, data: &[f32]) -> std::io::Res
     writeln!(writer, "P1")?;
     writeln!(writer, "{} {}", w, h)?;
 
-    let threshold = 0.5;
-
-    for (i, &pixel) in data.iter().enumerate() {
-        // Convert float to "0" or "1" based on threshold
-        let val = if pixel >= threshold { "1" } else { "0" };
-        write!(writer, "{}", val)?;
-
-        // Add a newline every 'w' pixels or spaces between values to keep it readable
-        if (i + 1) % w == 0 {
-            writeln!(writer)?;
-        } else {
-            write!(writer, " ")?;
-        }
+    // One line per image row with spaces between values to keep it readable
+    for row in data.chunks(w.max(1)) {
+        let line: Vec<&str> = row
+            .iter()
+            .map(|&pixel| match pixel_on(pixel, threshold, round_half_up) {
+                true => "1",
+                false => "0",
+            })
+            .collect();
+        writeln!(writer, "{}", line.join(" "))?;
     }
 
     writer.flush()?;
@@ -109,7 +561,7 @@ pub struct Trace {
     pub hide_axes: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PlotColor {
     Red,
     Blue,
@@ -119,9 +571,43 @@ pub enum PlotColor {
     Yellow,
     White,
     Reset,
+    /// Cyan and Magenta with the bold and dim styles `draw_line` uses for heavy and light weights
+    BoldCyan,
+    DimCyan,
+    BoldMagenta,
+    DimMagenta,
+}
+
+/// How the semantic plot colors are shown in the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Palette {
+    #[default]
+    Standard,
+    /// Shows Green as blue and Red as orange, which stay apart for red-green color blindness
+    ColorBlindSafe,
+}
+
+impl Palette {
+    /// Parses a palette name as given on the command line: `standard` or `colorblind`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "standard" => Some(Palette::Standard),
+            "colorblind" => Some(Palette::ColorBlindSafe),
+            _ => None,
+        }
+    }
 }
 
 impl PlotColor {
+    /// Escape code of the color in the given palette
+    pub fn to_ansi_in(&self, palette: Palette) -> &'static str {
+        match (palette, self) {
+            (Palette::ColorBlindSafe, PlotColor::Green) => "\x1b[94m",
+            (Palette::ColorBlindSafe, PlotColor::Red) => "\x1b[38;5;208m",
+            _ => self.to_ansi(),
+        }
+    }
+
     pub fn to_ansi(&self) -> &'static str {
         match self {
             PlotColor::Red => "\x1b[31m",
@@ -132,39 +618,68 @@ impl PlotColor {
             PlotColor::Yellow => "\x1b[33m",
             PlotColor::White => "\x1b[37m",
             PlotColor::Reset => "\x1b[0m",
+            PlotColor::BoldCyan => "\x1b[1;36m",
+            PlotColor::DimCyan => "\x1b[2;36m",
+            PlotColor::BoldMagenta => "\x1b[1;35m",
+            PlotColor::DimMagenta => "\x1b[2;35m",
         }
     }
 }
 
+/// Formats `val` right-aligned in `width` columns with `precision` decimals, in the
+/// `palette`'s green when the printed number is non-negative and its red otherwise. The value is rounded first and
+/// -0.0 becomes 0.0, so a tiny negative prints as a green "0.000" rather than "-0.000".
+pub fn format_signed(val: f32, width: usize, precision: usize, palette: Palette) -> String {
+    let factor = 10f32.powi(precision as i32);
+    let shown = (val * factor).round() / factor;
+    // -0.0 == 0.0, so this only replaces the sign of a zero
+    let shown = if shown == 0.0 { 0.0 } else { shown };
+
+    let color = if shown >= 0.0 {
+        PlotColor::Green
+    } else {
+        PlotColor::Red
+    };
+    format!(
+        "{}{:>width$.precision$}{}",
+        color.to_ansi_in(palette),
+        shown,
+        PlotColor::Reset.to_ansi()
+    )
+}
+
 // In image_utils.rs
 
 /// THE CORE LOGIC: Extracted so it can be reused without printing
+/// Builds the character grid of a plot. Returns an empty grid when `width` or `height`
+/// leave no room inside the axis margins.
 fn create_plot_grid(
     traces: &[Trace],
     width: usize,
     height: usize,
     fixed_bounds: Option<(f32, f32, f32, f32)>,
+    palette: Palette,
 ) -> Vec<Vec<String>> {
     let (min_x, max_x, min_y, max_y) = match fixed_bounds {
         Some(bounds) => bounds,
         None => get_bounds(traces),
     };
 
-    let margin_l = 10;
-    let margin_b = 2;
-    let plot_w = width - margin_l - 2;
-    let plot_h = height - margin_b - 2;
-
     let y_tick_count = 5;
     let x_tick_count = 4;
 
-    let mut grid = vec![vec![" ".to_string(); width]; height];
-
     let hide_all_axes = traces.iter().any(|t| t.hide_axes);
 
     let margin_l = if hide_all_axes { 1 } else { 10 };
     let margin_b = if hide_all_axes { 1 } else { 2 };
 
+    // The plot area below would underflow, a narrow terminal just gets no plot
+    if width <= margin_l + 2 || height <= margin_b + 2 {
+        return Vec::new();
+    }
+
+    let mut grid = vec![vec![" ".to_string(); width]; height];
+
     let plot_w = width - margin_l - 2;
     let plot_h = height - margin_b - 2;
 
@@ -197,21 +712,21 @@ fn create_plot_grid(
             }
         }
 
-        for y in 0..plot_h {
-            if grid[y][margin_l] == " " {
-                grid[y][margin_l] = "│".to_string();
+        for row in grid.iter_mut().take(plot_h) {
+            if row[margin_l] == " " {
+                row[margin_l] = "│".to_string();
             }
         }
-        for x in margin_l + 1..width {
-            if grid[plot_h][x] == " " {
-                grid[plot_h][x] = "─".to_string();
+        for cell in grid[plot_h].iter_mut().skip(margin_l + 1) {
+            if cell == " " {
+                *cell = "─".to_string();
             }
         }
         grid[plot_h][margin_l] = "└".to_string();
     }
 
     for trace in traces {
-        let color_code = trace.color.to_ansi();
+        let color_code = trace.color.to_ansi_in(palette);
         for i in 0..trace.x.len() {
             let px = map_val(trace.x[i], min_x, max_x, 0.0, plot_w as f32) as usize + margin_l + 1;
             let py = map_val(trace.y[i], min_y, max_y, plot_h as f32 - 1.0, 0.0) as usize;
@@ -240,7 +755,26 @@ pub fn render_plot(
     fixed_bounds: Option<(f32, f32, f32, f32)>,
     title: String,
 ) {
-    let grid = create_plot_grid(traces, width, height, fixed_bounds);
+    render_plot_palette(
+        traces,
+        width,
+        height,
+        fixed_bounds,
+        title,
+        Palette::Standard,
+    );
+}
+
+/// `render_plot` with the trace and legend colors taken from `palette`
+pub fn render_plot_palette(
+    traces: &[Trace],
+    width: usize,
+    height: usize,
+    fixed_bounds: Option<(f32, f32, f32, f32)>,
+    title: String,
+    palette: Palette,
+) {
+    let grid = create_plot_grid(traces, width, height, fixed_bounds, palette);
 
     let mut buffer = String::new();
     buffer.push_str("\x1b[2J\x1b[H\x1b[?25l");
@@ -252,6 +786,10 @@ pub fn render_plot(
     }
     buffer.push_str(&format!("\x1b[1;36m{}\x1b[0m\n\n", title.to_uppercase()));
 
+    if grid.is_empty() {
+        buffer.push_str(PLOT_TOO_SMALL);
+        buffer.push('\n');
+    }
     for row in grid {
         buffer.push_str(&row.concat());
         buffer.push('\n');
@@ -261,7 +799,7 @@ pub fn render_plot(
     for t in traces {
         buffer.push_str(&format!(
             "{} {} {} \x1b[0m  ",
-            t.color.to_ansi(),
+            t.color.to_ansi_in(palette),
             if t.is_line { "──" } else { "●" },
             t.name
         ));
@@ -270,6 +808,47 @@ pub fn render_plot(
     println!("\x1b[?25h");
 }
 
+/// Smallest terminal `terminal_size` reports, large enough for the axis margins
+pub const MIN_TERMINAL_WIDTH: usize = 20;
+pub const MIN_TERMINAL_HEIGHT: usize = 12;
+/// Printed instead of the grid when the requested size cannot fit the axes
+const PLOT_TOO_SMALL: &str = "(plot area too small, increase width and height)";
+/// Rows `render_plot` prints around the grid: blank lines, title and legend
+const PLOT_CHROME_ROWS: usize = 7;
+
+/// Terminal size in (columns, rows), read from the `COLUMNS` and `LINES` environment
+/// variables. Falls back to 80x24 when they are missing or invalid, and never goes
+/// below `MIN_TERMINAL_WIDTH` x `MIN_TERMINAL_HEIGHT`.
+pub fn terminal_size() -> (usize, usize) {
+    let read = |name: &str, fallback: usize| {
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(fallback)
+    };
+
+    (
+        read("COLUMNS", 80).max(MIN_TERMINAL_WIDTH),
+        read("LINES", 24).max(MIN_TERMINAL_HEIGHT),
+    )
+}
+
+/// `render_plot` sized to fill the terminal reported by `terminal_size`
+pub fn render_plot_auto(
+    traces: &[Trace],
+    fixed_bounds: Option<(f32, f32, f32, f32)>,
+    title: String,
+) {
+    let (width, height) = terminal_size();
+    render_plot(
+        traces,
+        width,
+        height - PLOT_CHROME_ROWS,
+        fixed_bounds,
+        title,
+    );
+}
+
 pub fn render_dual_plots(
     traces_left: &[Trace],
     traces_right: &[Trace],
@@ -278,8 +857,29 @@ pub fn render_dual_plots(
     bounds: Option<(f32, f32, f32, f32)>,
     title: String,
 ) {
-    let grid_l = create_plot_grid(traces_left, width, height, bounds);
-    let grid_r = create_plot_grid(traces_right, width, height, bounds);
+    render_dual_plots_palette(
+        traces_left,
+        traces_right,
+        width,
+        height,
+        bounds,
+        title,
+        Palette::Standard,
+    );
+}
+
+/// `render_dual_plots` with the trace and legend colors taken from `palette`
+pub fn render_dual_plots_palette(
+    traces_left: &[Trace],
+    traces_right: &[Trace],
+    width: usize,
+    height: usize,
+    bounds: Option<(f32, f32, f32, f32)>,
+    title: String,
+    palette: Palette,
+) {
+    let grid_l = create_plot_grid(traces_left, width, height, bounds, palette);
+    let grid_r = create_plot_grid(traces_right, width, height, bounds, palette);
 
     let mut buffer = String::new();
     buffer.push_str("\x1b[2J\x1b[H\x1b[?25l");
@@ -291,10 +891,14 @@ pub fn render_dual_plots(
         width = total_w
     ));
 
-    for y in 0..height {
-        buffer.push_str(&grid_l[y].concat());
+    if grid_l.is_empty() {
+        buffer.push_str(PLOT_TOO_SMALL);
+        buffer.push('\n');
+    }
+    for (row_l, row_r) in grid_l.iter().zip(&grid_r) {
+        buffer.push_str(&row_l.concat());
         buffer.push_str("    ");
-        buffer.push_str(&grid_r[y].concat());
+        buffer.push_str(&row_r.concat());
         buffer.push('\n');
     }
 
@@ -309,7 +913,7 @@ pub fn render_dual_plots(
         if !is_metadata && seen_names.insert(&t.name) {
             buffer.push_str(&format!(
                 "{} {} {} \x1b[0m  ",
-                t.color.to_ansi(),
+                t.color.to_ansi_in(palette),
                 if t.is_line { "──" } else { "●" },
                 t.name
             ));
@@ -321,7 +925,7 @@ pub fn render_dual_plots(
 }
 
 fn draw_line(
-    grid: &mut Vec<Vec<String>>,
+    grid: &mut [Vec<String>],
     x0: usize,
     y0: usize,
     x1: usize,
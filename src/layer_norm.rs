@@ -0,0 +1,184 @@
+use crate::Layer;
+use crate::tensor::Tensor;
+use crate::tensor::TensorError;
+
+/// Added to the variance so a constant row does not divide by zero
+const LAYER_NORM_EPSILON: f32 = 1e-5;
+
+/// Normalizes every row of a `[rows, features]` input with that row's own mean and
+/// variance, then applies a learnable per-feature scale `gamma` and shift `beta`.
+/// Unlike batch norm, the result of a row does not depend on the rest of the batch.
+pub struct LayerNorm {
+    gamma: Tensor,
+    beta: Tensor,
+    // Normalized input and 1 / sqrt(var + eps) of every row, both needed by the backward pass
+    normalized: Tensor,
+    inv_std: Tensor,
+    gamma_grad: Tensor,
+    beta_grad: Tensor,
+}
+
+impl Layer for LayerNorm {
+    fn forward(&mut self, input: &Tensor) -> Result<Tensor, TensorError> {
+        let (normalized, inv_std) = self.normalize(input)?;
+        let output = self.affine(&normalized)?;
+
+        self.normalized = normalized;
+        self.inv_std = inv_std;
+        Ok(output)
+    }
+
+    fn forward_infer(&self, input: &Tensor) -> Result<Tensor, TensorError> {
+        let (normalized, _) = self.normalize(input)?;
+        self.affine(&normalized)
+    }
+
+    fn backward(
+        &mut self,
+        output_error: &Tensor,
+        learning_rate: f32,
+    ) -> Result<Tensor, TensorError> {
+        let input_error = self.compute_gradients(output_error)?;
+        self.apply_gradients(learning_rate)?;
+
+        Ok(input_error)
+    }
+
+    fn compute_gradients(&mut self, output_error: &Tensor) -> Result<Tensor, TensorError> {
+        if output_error.shape() != self.normalized.shape() {
+            return Err(TensorError::ShapeMismatch);
+        }
+
+        // dL/dgamma sums err * x_hat over the rows, dL/dbeta sums err
+        self.gamma_grad = output_error.mul(&self.normalized)?.sum(Some(0))?;
+        self.beta_grad = output_error.sum(Some(0))?;
+
+        // With g = err * gamma, every row gets
+        // dx = inv_std * (g - mean(g) - x_hat * mean(g * x_hat))
+        let features = self.gamma.data().len();
+        let n = features as f32;
+        let mut centered = Vec::with_capacity(output_error.data().len());
+        for (err_row, x_hat) in output_error.rows().zip(self.normalized.rows()) {
+            let g: Vec<f32> = err_row
+                .iter()
+                .zip(self.gamma.data())
+                .map(|(e, w)| e * w)
+                .collect();
+            let g_mean = g.iter().sum::<f32>() / n;
+            let gx_mean = g.iter().zip(x_hat).map(|(g, x)| g * x).sum::<f32>() / n;
+
+            centered.extend(g.iter().zip(x_hat).map(|(g, x)| g - g_mean - x * gx_mean));
+        }
+
+        Tensor::new(centered, output_error.shape().to_vec())?.scale_rows(&self.inv_std)
+    }
+
+    fn grads(&self) -> Vec<&Tensor> {
+        vec![&self.gamma_grad, &self.beta_grad]
+    }
+
+    fn scale_grads(&mut self, factor: f32) -> Result<(), TensorError> {
+        self.gamma_grad = self.gamma_grad.scale(&factor)?;
+        self.beta_grad = self.beta_grad.scale(&factor)?;
+        Ok(())
+    }
+
+    fn apply_gradients(&mut self, learning_rate: f32) -> Result<(), TensorError> {
+        // Nothing was computed yet, so there is nothing to apply
+        if self.gamma_grad.shape() != self.gamma.shape() {
+            return Ok(());
+        }
+
+        self.gamma = self.gamma.sub(&self.gamma_grad.scale(&learning_rate)?)?;
+        self.beta = self.beta.sub(&self.beta_grad.scale(&learning_rate)?)?;
+        Ok(())
+    }
+
+    fn num_params(&self) -> usize {
+        self.gamma.data().len() + self.beta.data().len()
+    }
+
+    fn params(&self) -> Vec<&Tensor> {
+        vec![&self.gamma, &self.beta]
+    }
+
+    fn set_params(&mut self, params: Vec<Tensor>) -> Result<(), TensorError> {
+        match <[Tensor; 2]>::try_from(params) {
+            Ok([gamma, beta])
+                if gamma.shape() == self.gamma.shape() && beta.shape() == self.beta.shape() =>
+            {
+                self.gamma = gamma;
+                self.beta = beta;
+                Ok(())
+            }
+            _ => Err(TensorError::ShapeMismatch),
+        }
+    }
+
+    fn output_shape(&self, input_shape: &[usize]) -> Result<Vec<usize>, TensorError> {
+        match input_shape {
+            [_, features] if *features == self.gamma.data().len() => Ok(input_shape.to_vec()),
+            [_, _] => Err(TensorError::ShapeMismatch),
+            _ => Err(TensorError::InvalidRank),
+        }
+    }
+}
+
+impl LayerNorm {
+    /// Starts as a plain normalization: gamma is all ones and beta all zeros
+    pub fn new(features: usize) -> Self {
+        LayerNorm {
+            gamma: Tensor::new(vec![1.0; features], vec![features]).unwrap(),
+            beta: Tensor::new(vec![0.0; features], vec![features]).unwrap(),
+            normalized: Tensor::empty(),
+            inv_std: Tensor::empty(),
+            gamma_grad: Tensor::empty(),
+            beta_grad: Tensor::empty(),
+        }
+    }
+
+    pub fn gamma(&self) -> &Tensor {
+        &self.gamma
+    }
+
+    pub fn beta(&self) -> &Tensor {
+        &self.beta
+    }
+
+    /// (x - mean) / sqrt(var + eps) of every row, plus the `[rows]` tensor of 1 / sqrt(var + eps)
+    fn normalize(&self, input: &Tensor) -> Result<(Tensor, Tensor), TensorError> {
+        self.output_shape(input.shape())?;
+
+        let n = self.gamma.data().len() as f32;
+        let mut normalized = Vec::with_capacity(input.data().len());
+        let mut inv_std = Vec::with_capacity(input.shape()[0]);
+        for row in input.rows() {
+            let mean = row.iter().sum::<f32>() / n;
+            let var = row.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / n;
+            let inv = 1.0 / (var + LAYER_NORM_EPSILON).sqrt();
+
+            normalized.extend(row.iter().map(|v| (v - mean) * inv));
+            inv_std.push(inv);
+        }
+
+        let rows = inv_std.len();
+        Ok((
+            Tensor::new(normalized, input.shape().to_vec())?,
+            Tensor::new(inv_std, vec![rows])?,
+        ))
+    }
+
+    /// x_hat * gamma + beta, with gamma and beta shared by all rows
+    fn affine(&self, normalized: &Tensor) -> Result<Tensor, TensorError> {
+        let data = normalized
+            .rows()
+            .flat_map(|row| {
+                row.iter()
+                    .zip(self.gamma.data().iter().zip(self.beta.data()))
+                    .map(|(x, (g, b))| x * g + b)
+            })
+            .collect();
+
+        Tensor::new(data, normalized.shape().to_vec())
+    }
+}
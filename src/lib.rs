@@ -1,12 +1,17 @@
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
 use crate::tensor::{Tensor, TensorError};
 
 pub mod activation;
+pub mod data;
 pub mod examples;
-pub mod image_generator;
 pub mod image_utils;
+pub mod layer_norm;
 pub mod linear;
 pub mod loss;
+pub mod metrics;
 pub mod neural_network;
+pub mod residual;
 pub mod tensor;
 
 pub trait Rng {
@@ -16,7 +21,67 @@ pub trait Rng {
     }
 }
 
+/// Linear congruential generator: cheap and fully determined by the seed, so runs and
+/// tests built on it repeat exactly
+#[derive(Debug, Clone)]
+pub struct DefaultRng {
+    state: u64,
+}
+
+impl DefaultRng {
+    pub fn new(seed: u64) -> Self {
+        DefaultRng { state: seed }
+    }
+}
+
+impl Rng for DefaultRng {
+    fn next_u32(&mut self) -> i32 {
+        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        (self.state >> 32) as u32 as i32
+    }
+}
+
 pub trait Layer {
     fn forward(&mut self, input: &Tensor) -> Result<Tensor, TensorError>;
+    /// Forward pass for inference only: nothing is cached for backpropagation
+    fn forward_infer(&self, input: &Tensor) -> Result<Tensor, TensorError>;
     fn backward(&mut self, output_error: &Tensor, learning_rate: f32) -> Result<Tensor, TensorError>;
+    /// Number of trainable values held by the layer
+    fn num_params(&self) -> usize {
+        0
+    }
+    /// Trainable tensors in a fixed order, used to save checkpoints
+    fn params(&self) -> Vec<&Tensor> {
+        Vec::new()
+    }
+    /// Replaces the trainable tensors, given in the order of `params`
+    fn set_params(&mut self, params: Vec<Tensor>) -> Result<(), TensorError> {
+        if params.is_empty() {
+            Ok(())
+        } else {
+            Err(TensorError::ShapeMismatch)
+        }
+    }
+    /// First half of `backward`: stores the parameter gradients without applying them and
+    /// returns the error for the previous layer. Parameter-free layers just backpropagate.
+    fn compute_gradients(&mut self, output_error: &Tensor) -> Result<Tensor, TensorError> {
+        self.backward(output_error, 0.0)
+    }
+    /// Gradients stored by `compute_gradients`, in the order of `params`
+    fn grads(&self) -> Vec<&Tensor> {
+        Vec::new()
+    }
+    /// Multiplies every stored gradient by `factor`
+    fn scale_grads(&mut self, _factor: f32) -> Result<(), TensorError> {
+        Ok(())
+    }
+    /// Second half of `backward`: steps the parameters against the stored gradients
+    fn apply_gradients(&mut self, _learning_rate: f32) -> Result<(), TensorError> {
+        Ok(())
+    }
+    /// Shape `forward` would return for an input of `input_shape`, without touching any data.
+    /// The default suits element-wise layers, which keep the shape unchanged.
+    fn output_shape(&self, input_shape: &[usize]) -> Result<Vec<usize>, TensorError> {
+        Ok(input_shape.to_vec())
+    }
 }
@@ -4,9 +4,32 @@ use crate::tensor::Tensor;
 use crate::tensor::TensorError;
 use std::vec;
 
+/// Weight initialization schemes for a Linear layer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InitScheme {
+    /// Uniform in [-1, 1]
+    Uniform,
+    /// Uniform in [-limit, limit] with limit = sqrt(6 / (in + out))
+    Xavier,
+    /// Uniform in [-limit, limit] with limit = sqrt(6 / ((1 + slope^2) * in)),
+    /// suited to layers followed by a (leaky) ReLU with the given negative slope
+    KaimingUniform { negative_slope: f32 },
+}
+
+/// Scheme used by `Linear::new`
+pub const DEFAULT_INIT: InitScheme = InitScheme::KaimingUniform {
+    negative_slope: 0.0,
+};
+
 pub struct Linear {
     weight: Tensor,
     input: Tensor,
+    // Scratch buffer for dL/dW, reused every backward pass since its shape never changes
+    weight_grad: Tensor,
+    // `[out]` vector added to every output row, only used (and trained) with `use_bias`
+    bias: Tensor,
+    bias_grad: Tensor,
+    use_bias: bool,
 }
 
 impl Layer for Linear {
@@ -15,7 +38,64 @@ impl Layer for Linear {
         // to calculate the gradient: dL/dW = input.T * output_error
         self.input = Tensor::new(input.data().to_vec(), input.shape().to_vec())?;
 
-        input.matmul(&self.weight)
+        self.forward_infer(input)
+    }
+
+    fn forward_infer(&self, input: &Tensor) -> Result<Tensor, TensorError> {
+        let output = input.matmul(&self.weight)?;
+        if !self.use_bias {
+            return Ok(output);
+        }
+
+        let data = output
+            .rows()
+            .flat_map(|row| row.iter().zip(self.bias.data()).map(|(v, b)| v + b))
+            .collect();
+        Tensor::new(data, output.shape().to_vec())
+    }
+
+    fn num_params(&self) -> usize {
+        self.params().iter().map(|p| p.data().len()).sum()
+    }
+
+    fn params(&self) -> Vec<&Tensor> {
+        match self.use_bias {
+            true => vec![&self.weight, &self.bias],
+            false => vec![&self.weight],
+        }
+    }
+
+    fn set_params(&mut self, params: Vec<Tensor>) -> Result<(), TensorError> {
+        let mut params = params.into_iter();
+        match (params.next(), params.next(), params.next()) {
+            (Some(weight), None, None)
+                if !self.use_bias && weight.shape() == self.weight.shape() =>
+            {
+                self.weight = weight;
+                Ok(())
+            }
+            (Some(weight), Some(bias), None)
+                if self.use_bias
+                    && weight.shape() == self.weight.shape()
+                    && bias.shape() == self.bias.shape() =>
+            {
+                self.weight = weight;
+                self.bias = bias;
+                Ok(())
+            }
+            _ => Err(TensorError::ShapeMismatch),
+        }
+    }
+
+    fn output_shape(&self, input_shape: &[usize]) -> Result<Vec<usize>, TensorError> {
+        let (in_features, out_features) = (self.weight.shape()[0], self.weight.shape()[1]);
+
+        match input_shape {
+            [c] if *c == in_features => Ok(vec![out_features]),
+            [rows, c] if *c == in_features => Ok(vec![*rows, out_features]),
+            [_] | [_, _] => Err(TensorError::ShapeMismatch),
+            _ => Err(TensorError::InvalidRank),
+        }
     }
 
     fn backward(
@@ -23,32 +103,128 @@ impl Layer for Linear {
         output_error: &Tensor,
         learning_rate: f32,
     ) -> Result<Tensor, TensorError> {
+        let input_error = self.compute_gradients(output_error)?;
+        self.apply_gradients(learning_rate)?;
+
+        Ok(input_error)
+    }
+
+    fn compute_gradients(&mut self, output_error: &Tensor) -> Result<Tensor, TensorError> {
         let weight_t = self.weight.transpose()?;
         let input_error = output_error.matmul(&weight_t)?;
 
         let input_t = self.input.transpose()?;
-        let weights_grad = input_t.matmul(output_error)?;
+        if self.weight_grad.shape() != self.weight.shape() {
+            self.weight_grad = Tensor::new(
+                vec![0.0; self.weight.data().len()],
+                self.weight.shape().to_vec(),
+            )?;
+        }
+        input_t.matmul_into(output_error, &mut self.weight_grad)?;
 
-        let weight_step = weights_grad.scale(&learning_rate)?;
-        self.weight = self.weight.sub(&weight_step)?;
+        if self.use_bias {
+            // dL/db sums the error over the rows; a single 1D sample is its own sum
+            self.bias_grad = match output_error.shape().len() {
+                1 => output_error.clone(),
+                _ => output_error.sum(Some(0))?,
+            };
+        }
 
         Ok(input_error)
     }
+
+    fn grads(&self) -> Vec<&Tensor> {
+        match self.use_bias {
+            true => vec![&self.weight_grad, &self.bias_grad],
+            false => vec![&self.weight_grad],
+        }
+    }
+
+    fn scale_grads(&mut self, factor: f32) -> Result<(), TensorError> {
+        self.weight_grad = self.weight_grad.scale(&factor)?;
+        if self.use_bias {
+            self.bias_grad = self.bias_grad.scale(&factor)?;
+        }
+        Ok(())
+    }
+
+    fn apply_gradients(&mut self, learning_rate: f32) -> Result<(), TensorError> {
+        let weight_step = self.weight_grad.scale(&learning_rate)?;
+        self.weight = self.weight.sub(&weight_step)?;
+
+        if self.use_bias {
+            let bias_step = self.bias_grad.scale(&learning_rate)?;
+            self.bias = self.bias.sub(&bias_step)?;
+        }
+        Ok(())
+    }
 }
 
 impl Linear {
+    /// Initializes the weights with `DEFAULT_INIT`
     pub fn new(in_features: usize, out_features: usize, rng: &mut dyn Rng) -> Self {
+        Self::with_init(in_features, out_features, DEFAULT_INIT, rng)
+    }
+
+    /// Same as `new`, but guaranteed bias-free, for call sites that must never get a bias
+    /// (a layer followed by a normalization, for example)
+    pub fn new_no_bias(in_features: usize, out_features: usize, rng: &mut dyn Rng) -> Self {
+        let mut layer = Self::new(in_features, out_features, rng);
+        layer.use_bias = false;
+        layer
+    }
+
+    /// Same as `new`, plus a trainable `[out]` bias starting at zero. The classification
+    /// examples get their bias from a constant 1.0 input column instead.
+    pub fn with_bias(in_features: usize, out_features: usize, rng: &mut dyn Rng) -> Self {
+        let mut layer = Self::new(in_features, out_features, rng);
+        layer.use_bias = true;
+        layer
+    }
+
+    /// Initializes the weights uniformly in [-1, 1]
+    pub fn new_uniform(in_features: usize, out_features: usize, rng: &mut dyn Rng) -> Self {
+        Self::with_init(in_features, out_features, InitScheme::Uniform, rng)
+    }
+
+    pub fn with_init(
+        in_features: usize,
+        out_features: usize,
+        init: InitScheme,
+        rng: &mut dyn Rng,
+    ) -> Self {
+        // next_f32 already yields values in [-1, 1], so each scheme only picks the scale
+        let limit = match init {
+            InitScheme::Uniform => 1.0,
+            InitScheme::Xavier => (6.0 / (in_features + out_features) as f32).sqrt(),
+            InitScheme::KaimingUniform { negative_slope } => {
+                (6.0 / ((1.0 + negative_slope * negative_slope) * in_features as f32)).sqrt()
+            }
+        };
+
         let weights = (0..in_features * out_features)
-            .map(|_| rng.next_f32())
+            .map(|_| rng.next_f32() * limit)
             .collect();
 
         let weight = Tensor::new(weights, vec![in_features, out_features]).unwrap();
+        let weight_grad = Tensor::new(
+            vec![0.0; in_features * out_features],
+            vec![in_features, out_features],
+        )
+        .unwrap();
+
+        let bias = Tensor::new(vec![0.0; out_features], vec![out_features]).unwrap();
+        let bias_grad = bias.clone();
 
         let empty = Tensor::empty();
 
         Linear {
             weight,
             input: empty,
+            weight_grad,
+            bias,
+            bias_grad,
+            use_bias: false,
         }
     }
 
@@ -56,6 +232,10 @@ impl Linear {
         &self.weight
     }
 
+    pub fn bias(&self) -> Option<&Tensor> {
+        self.use_bias.then_some(&self.bias)
+    }
+
     pub fn set_weight(&mut self, t: Tensor) {
         self.weight = t;
     }
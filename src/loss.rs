@@ -1,15 +1,22 @@
+use crate::activation::{stable_sigmoid, stable_softmax};
 use crate::tensor::Tensor;
 use crate::tensor::TensorError;
 
+/// 1 / n for averaging, and 0 for an empty tensor so its loss is a defined 0.0
+/// instead of 0 * inf = NaN
+fn inverse_count(n: usize) -> f32 {
+    if n == 0 { 0.0 } else { 1.0 / n as f32 }
+}
+
 pub fn l1_loss(predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
     if predicted.shape() != actual.shape() {
         return Err(TensorError::ShapeMismatch);
     }
 
-    let n = predicted.shape().iter().product::<usize>() as f32;
+    let n = predicted.shape().iter().product::<usize>();
 
     let diff = predicted.sub(actual)?.abs()?;
-    diff.sum(None)?.scale(&(1.0 / n))
+    diff.sum(None)?.scale(&inverse_count(n))
 }
 
 pub fn mse_loss(predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
@@ -17,20 +24,103 @@ pub fn mse_loss(predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorErr
         return Err(TensorError::ShapeMismatch);
     }
 
-    let n = predicted.shape().iter().product::<usize>() as f32;
+    let n = predicted.shape().iter().product::<usize>();
 
     predicted
         .sub(actual)?
-        .powf(2.0)?
+        .square()?
         .sum(None)?
-        .scale(&(1.0 / n))
+        .scale(&inverse_count(n))
+}
+
+/// `l1_loss` with a compensated sum, for large tensors where plain `f32` accumulation drifts
+pub fn l1_loss_kahan(predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
+    if predicted.shape() != actual.shape() {
+        return Err(TensorError::ShapeMismatch);
+    }
+
+    let n = predicted.shape().iter().product::<usize>();
+
+    let diff = predicted.sub(actual)?.abs()?;
+    diff.sum_kahan(None)?.scale(&inverse_count(n))
+}
+
+/// `mse_loss` with a compensated sum, for large tensors where plain `f32` accumulation drifts
+pub fn mse_loss_kahan(predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
+    if predicted.shape() != actual.shape() {
+        return Err(TensorError::ShapeMismatch);
+    }
+
+    let n = predicted.shape().iter().product::<usize>();
+
+    predicted
+        .sub(actual)?
+        .square()?
+        .sum_kahan(None)?
+        .scale(&inverse_count(n))
+}
+
+/// MSE of every row of a 2D prediction, as an `[n]` tensor. Its mean is `mse_loss`.
+pub fn mse_loss_per_sample(predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
+    if predicted.shape() != actual.shape() {
+        return Err(TensorError::ShapeMismatch);
+    }
+    if predicted.shape().len() != 2 {
+        return Err(TensorError::InvalidRank);
+    }
+
+    let cols = predicted.shape()[1];
+
+    predicted
+        .sub(actual)?
+        .square()?
+        .sum(Some(1))?
+        .scale(&inverse_count(cols))
+}
+
+/// Keeps `ln` away from 0 when a prediction saturates at exactly 0 or 1
+const BCE_EPSILON: f32 = 1e-7;
+
+/// Binary cross-entropy of every row of a 2D prediction of probabilities, as an `[n]` tensor:
+/// -mean(actual * ln(p) + (1 - actual) * ln(1 - p)) over the columns
+pub fn bce_loss_per_sample(predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
+    if predicted.shape() != actual.shape() {
+        return Err(TensorError::ShapeMismatch);
+    }
+    if predicted.shape().len() != 2 {
+        return Err(TensorError::InvalidRank);
+    }
+
+    let cols = predicted.shape()[1];
+
+    let terms: Vec<f32> = predicted
+        .data()
+        .iter()
+        .zip(actual.data())
+        .map(|(&p, &y)| {
+            let p = p.clamp(BCE_EPSILON, 1.0 - BCE_EPSILON);
+            y * p.ln() + (1.0 - y) * (1.0 - p).ln()
+        })
+        .collect();
+
+    Tensor::new(terms, predicted.shape().to_vec())?
+        .sum(Some(1))?
+        .scale(&-inverse_count(cols))
+}
+
+/// Mean binary cross-entropy over all rows, as a `[1]` tensor.
+/// The loss that `bce_sigmoid_delta` is the gradient of.
+pub fn bce_loss(predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
+    let per_sample = bce_loss_per_sample(predicted, actual)?;
+    let rows = per_sample.shape()[0];
+    per_sample.sum(None)?.scale(&inverse_count(rows))
 }
 
 pub fn mse_loss_gradient(predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
     // Gradient of MSE: 2/n * (predicted - actual)
     let diff = predicted.sub(actual)?;
-    let n = predicted.shape()[0] as f32;
-    diff.scale(&(2.0 / n))
+    let n = predicted.shape()[0];
+    diff.scale(&(2.0 * inverse_count(n)))
 }
 
 pub fn bce_sigmoid_delta(predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
@@ -38,7 +128,129 @@ pub fn bce_sigmoid_delta(predicted: &Tensor, actual: &Tensor) -> Result<Tensor,
         return Err(TensorError::ShapeMismatch);
     }
 
-    let n = predicted.shape().iter().product::<usize>() as f32;
-    
-    predicted.sub(actual)?.scale(&(1.0 / n))
+    let n = predicted.shape().iter().product::<usize>();
+
+    predicted.sub(actual)?.scale(&inverse_count(n))
+}
+
+/// Fused sigmoid + BCE delta that takes the raw logits of the output layer,
+/// so the network can end in a Linear layer instead of an explicit Sigmoid
+pub fn bce_with_logits_delta(logits: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
+    if logits.shape() != actual.shape() {
+        return Err(TensorError::ShapeMismatch);
+    }
+
+    bce_sigmoid_delta(&stable_sigmoid(logits)?, actual)
+}
+
+/// Same as `bce_sigmoid_delta`, but the rows whose target is 1 are scaled by `pos_weight`
+/// so an under-represented positive class pulls harder on the weights
+pub fn bce_weighted_delta(
+    predicted: &Tensor,
+    actual: &Tensor,
+    pos_weight: f32,
+) -> Result<Tensor, TensorError> {
+    if predicted.shape() != actual.shape() {
+        return Err(TensorError::ShapeMismatch);
+    }
+
+    let n = predicted.shape().iter().product::<usize>();
+
+    let weights: Vec<f32> = actual
+        .data()
+        .iter()
+        .map(|&a| if a == 1.0 { pos_weight } else { 1.0 })
+        .collect();
+    let weights = Tensor::new(weights, actual.shape().to_vec())?;
+
+    predicted
+        .sub(actual)?
+        .mul(&weights)?
+        .scale(&inverse_count(n))
+}
+
+/// Mean cross-entropy of the softmax of 2D `logits` against one-hot (or any probability)
+/// rows in `actual`: -mean over rows of sum(actual * log_softmax(logits))
+pub fn softmax_cross_entropy_loss(logits: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
+    if logits.shape() != actual.shape() {
+        return Err(TensorError::ShapeMismatch);
+    }
+    if logits.shape().len() != 2 {
+        return Err(TensorError::InvalidRank);
+    }
+
+    let lse = logits.logsumexp(Some(1))?;
+    let total: f32 = logits
+        .rows()
+        .zip(actual.rows())
+        .zip(lse.data())
+        .map(|((z, y), &lse)| z.iter().zip(y).map(|(&z, &y)| y * (lse - z)).sum::<f32>())
+        .sum();
+
+    Tensor::new(vec![total * inverse_count(logits.shape()[0])], vec![1])
+}
+
+/// Gradient of `softmax_cross_entropy_loss` with respect to the logits:
+/// (softmax(logits) - actual) / rows
+pub fn softmax_cross_entropy_delta(
+    logits: &Tensor,
+    actual: &Tensor,
+) -> Result<Tensor, TensorError> {
+    if logits.shape() != actual.shape() {
+        return Err(TensorError::ShapeMismatch);
+    }
+
+    stable_softmax(logits)?
+        .sub(actual)?
+        .scale(&inverse_count(logits.shape()[0]))
+}
+
+/// Checks that there is one label per row of 2D `logits` and every label names a column
+fn check_labels(logits: &Tensor, labels: &[usize]) -> Result<(), TensorError> {
+    let [rows, cols] = logits.shape()[..] else {
+        return Err(TensorError::InvalidRank);
+    };
+    if labels.len() != rows {
+        return Err(TensorError::ShapeMismatch);
+    }
+    if labels.iter().any(|&l| l >= cols) {
+        return Err(TensorError::IndexOutOfBounds);
+    }
+    Ok(())
+}
+
+/// `softmax_cross_entropy_loss` with class indices instead of one-hot rows,
+/// so no one-hot matrix is built: -mean(log_softmax(logits)[row, label])
+pub fn softmax_cross_entropy_loss_from_labels(
+    logits: &Tensor,
+    labels: &[usize],
+) -> Result<Tensor, TensorError> {
+    check_labels(logits, labels)?;
+
+    let lse = logits.logsumexp(Some(1))?;
+    let total: f32 = logits
+        .rows()
+        .zip(labels)
+        .zip(lse.data())
+        .map(|((z, &label), &lse)| lse - z[label])
+        .sum();
+
+    Tensor::new(vec![total * inverse_count(labels.len())], vec![1])
+}
+
+/// `softmax_cross_entropy_delta` with class indices instead of one-hot rows:
+/// the softmax, minus 1 at every label, divided by the row count
+pub fn softmax_cross_entropy_delta_from_labels(
+    logits: &Tensor,
+    labels: &[usize],
+) -> Result<Tensor, TensorError> {
+    check_labels(logits, labels)?;
+
+    let mut delta = stable_softmax(logits)?.data().to_vec();
+    let cols = logits.shape()[1];
+    for (r, &label) in labels.iter().enumerate() {
+        delta[r * cols + label] -= 1.0;
+    }
+
+    Tensor::new(delta, logits.shape().to_vec())?.scale(&inverse_count(labels.len()))
 }
@@ -1,6 +1,11 @@
+use build_your_own_nn::DefaultRng;
 use build_your_own_nn::Rng;
-use build_your_own_nn::examples::benchmark::run_benchmark;
+use build_your_own_nn::examples::AnimationConfig;
+use build_your_own_nn::examples::benchmark::{
+    BENCHMARK_SIZES, run_benchmark, run_benchmark_csv, run_network_benchmark,
+};
 use build_your_own_nn::examples::image_reconstructor;
+use build_your_own_nn::examples::image_reconstructor::ReconstructorConfig;
 use build_your_own_nn::examples::linear_regression;
 use build_your_own_nn::examples::linear_regression_animated;
 use build_your_own_nn::examples::neural_network_logic;
@@ -13,17 +18,41 @@ use build_your_own_nn::examples::neural_network_xor_animated;
 
 use build_your_own_nn::examples::two_moons;
 use build_your_own_nn::examples::two_moons_3layers;
+use build_your_own_nn::image_utils::{PBM_THRESHOLD, Palette};
 use build_your_own_nn::tensor::TensorError;
 use std::io::{self, Write};
 
-struct SimpleRng {
-    state: u64,
+/// Settings given on the command line
+struct Options {
+    /// `--frames <dir>`: also save every Two Moons frame as a numbered PBM in `dir`
+    frame_dir: Option<String>,
+    /// `--palette <standard|colorblind>`: colors of every animated plot
+    palette: Palette,
 }
 
-impl Rng for SimpleRng {
-    fn next_u32(&mut self) -> i32 {
-        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1);
-        (self.state >> 32) as u32 as i32
+impl Options {
+    /// The example's own playback with the command line palette
+    fn animation(&self, config: AnimationConfig) -> AnimationConfig {
+        AnimationConfig {
+            palette: self.palette,
+            ..config
+        }
+    }
+}
+
+fn parse_options() -> Options {
+    let args: Vec<String> = std::env::args().collect();
+    let value_of = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1).cloned())
+    };
+
+    Options {
+        frame_dir: value_of("--frames"),
+        palette: value_of("--palette")
+            .and_then(|name| Palette::from_name(&name))
+            .unwrap_or_default(),
     }
 }
 
@@ -39,7 +68,7 @@ fn get_user_choice(length: usize) -> usize {
     input.trim().parse().unwrap_or(0)
 }
 
-fn run_user_io(rng: &mut dyn Rng) -> Result<(), TensorError> {
+fn run_user_io(rng: &mut dyn Rng, cli: &Options) -> Result<(), TensorError> {
     let options = vec![
         "Simple Linear Regression",
         "Animated Linear Regression",
@@ -59,6 +88,9 @@ fn run_user_io(rng: &mut dyn Rng) -> Result<(), TensorError> {
         "Run Two Moons Example with 3 Layers",
         "Image Reconstructor",
         "Run Matrix Multiplication Benchmark",
+        "Run Network Training Benchmark",
+        "AND + OR Gates with One Network",
+        "Matrix Multiplication Benchmark as CSV",
         "Exit",
     ];
 
@@ -72,37 +104,105 @@ fn run_user_io(rng: &mut dyn Rng) -> Result<(), TensorError> {
         println!("==========================================");
 
         match get_user_choice(options.len()) {
-            1 => linear_regression::linear_regression(rng)?,
-            2 => linear_regression_animated::linear_regression(rng)?,
-            3 => neural_network_not_animated::not_neural_network(rng)?,
+            1 => {
+                linear_regression::linear_regression(rng)?;
+            }
+            2 => linear_regression_animated::linear_regression(
+                rng,
+                &cli.animation(linear_regression_animated::animation_config()),
+            )?,
+            3 => neural_network_not_animated::not_neural_network(
+                rng,
+                &cli.animation(neural_network_not_animated::animation_config()),
+            )?,
 
             // Static Binary Gates
-            4 => neural_network_logic::demonstrate_logic(rng, Gate::AND)?,
-            5 => neural_network_logic::demonstrate_logic(rng, Gate::OR)?,
-            6 => neural_network_logic::demonstrate_logic(rng, Gate::NAND)?,
-            7 => neural_network_logic::demonstrate_logic(rng, Gate::NOR)?,
+            4 => {
+                neural_network_logic::demonstrate_logic(rng, Gate::AND)?;
+            }
+            5 => {
+                neural_network_logic::demonstrate_logic(rng, Gate::OR)?;
+            }
+            6 => {
+                neural_network_logic::demonstrate_logic(rng, Gate::NAND)?;
+            }
+            7 => {
+                neural_network_logic::demonstrate_logic(rng, Gate::NOR)?;
+            }
 
             // Animated Gate
-            8 => neural_network_logic_animated::demonstrate_logic(rng, AnimatedGate::AND)?,
-            9 => neural_network_logic_animated::demonstrate_logic(rng, AnimatedGate::OR)?,
-            10 => neural_network_logic_animated::demonstrate_logic(rng, AnimatedGate::NAND)?,
-            11 => neural_network_logic_animated::demonstrate_logic(rng, AnimatedGate::NOR)?,
+            8 => neural_network_logic_animated::demonstrate_logic(
+                rng,
+                AnimatedGate::AND,
+                &cli.animation(neural_network_logic_animated::animation_config()),
+            )?,
+            9 => neural_network_logic_animated::demonstrate_logic(
+                rng,
+                AnimatedGate::OR,
+                &cli.animation(neural_network_logic_animated::animation_config()),
+            )?,
+            10 => neural_network_logic_animated::demonstrate_logic(
+                rng,
+                AnimatedGate::NAND,
+                &cli.animation(neural_network_logic_animated::animation_config()),
+            )?,
+            11 => neural_network_logic_animated::demonstrate_logic(
+                rng,
+                AnimatedGate::NOR,
+                &cli.animation(neural_network_logic_animated::animation_config()),
+            )?,
 
             // XOR Logic (Requires hidden layers)
-            12 => neural_network_xor::xor_neural_network(rng)?,
-            13 => neural_network_xor_animated::xor_neural_network(rng, false)?,
-            14 => neural_network_xor_animated::xor_neural_network(rng, true)?,
+            12 => {
+                neural_network_xor::xor_neural_network(rng)?;
+            }
+            13 => neural_network_xor_animated::xor_neural_network(
+                rng,
+                false,
+                &cli.animation(neural_network_xor_animated::animation_config()),
+            )?,
+            14 => neural_network_xor_animated::xor_neural_network(
+                rng,
+                true,
+                &cli.animation(neural_network_xor_animated::animation_config()),
+            )?,
 
             // Classic Two Moons Data
-            15 => two_moons::two_moons_neural_network(rng)?,
-            16 => two_moons_3layers::two_moons_neural_network(rng)?,
-
-            17 => match image_reconstructor::reconstruct_image("assets/spiral_25.pbm", 150, rng) {
+            15 => {
+                let config = AnimationConfig {
+                    frame_dir: cli.frame_dir.clone(),
+                    ..cli.animation(two_moons::animation_config())
+                };
+                if let Err(err) = two_moons::two_moons_neural_network(rng, &config) {
+                    println!("Error: {:?}", err);
+                }
+            }
+            16 => two_moons_3layers::two_moons_neural_network(
+                rng,
+                &cli.animation(two_moons_3layers::animation_config()),
+            )?,
+
+            17 => match image_reconstructor::reconstruct_image(
+                "assets/spiral_25.pbm",
+                150,
+                PBM_THRESHOLD,
+                &ReconstructorConfig::default(),
+                rng,
+            ) {
                 Ok(_) => println!("Done"),
                 Err(err) => println!("Error: {:?}", err),
             },
             18 => run_benchmark(),
-            19 | _ => {
+            // Same sample count as the 150x150 reconstruction
+            19 => match run_network_benchmark(rng, 150 * 150, 10) {
+                Ok(_) => println!("Done"),
+                Err(err) => println!("Error: {:?}", err),
+            },
+            20 => {
+                neural_network_logic::demonstrate_multi_gate(rng)?;
+            }
+            21 => print!("{}", run_benchmark_csv(&BENCHMARK_SIZES, ',')),
+            _ => {
                 println!("Goodbye!");
                 break;
             }
@@ -117,6 +217,8 @@ fn run_user_io(rng: &mut dyn Rng) -> Result<(), TensorError> {
 }
 
 fn main() {
-    let mut rng = SimpleRng { state: 73 };
-    run_user_io(&mut rng);
+    let mut rng = DefaultRng::new(73);
+    if let Err(err) = run_user_io(&mut rng, &parse_options()) {
+        println!("Error: {}", err);
+    }
 }
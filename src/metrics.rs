@@ -0,0 +1,24 @@
+use crate::tensor::{Tensor, TensorError};
+
+/// Mean absolute percentage error: mean(|(actual - predicted) / actual|) * 100.
+/// Elements whose target is exactly 0 have no defined percentage and are left out of the
+/// mean; when no element is left the error is 0.0, like the losses on empty tensors.
+pub fn mape(predicted: &Tensor, actual: &Tensor) -> Result<f32, TensorError> {
+    if predicted.shape() != actual.shape() {
+        return Err(TensorError::ShapeMismatch);
+    }
+
+    let (total, count) = predicted
+        .data()
+        .iter()
+        .zip(actual.data())
+        .filter(|&(_, &a)| a != 0.0)
+        .fold((0.0, 0), |(total, count), (&p, &a)| {
+            (total + ((a - p) / a).abs(), count + 1)
+        });
+
+    if count == 0 {
+        return Ok(0.0);
+    }
+    Ok(total / count as f32 * 100.0)
+}
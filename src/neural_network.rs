@@ -1,12 +1,80 @@
-use crate::Layer;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::time::{Duration, Instant};
+
+use crate::activation::{Activation, ActivationType};
+use crate::data::{shuffle_rows, split_rows};
+use crate::linear::{InitScheme, Linear};
+use crate::loss::mse_loss;
 use crate::tensor::{Tensor, TensorError};
+use crate::{Layer, Rng};
 
 /// Type alias for the loss gradient function pointer
-type LossGradFn = fn(&Tensor, &Tensor) -> Result<Tensor, TensorError>;
+pub type LossGradFn = fn(&Tensor, &Tensor) -> Result<Tensor, TensorError>;
+
+/// Type alias for the loss function pointer, returning the loss as a `[1]` tensor
+pub type LossFn = fn(&Tensor, &Tensor) -> Result<Tensor, TensorError>;
+
+/// First line of every file written by `Network::save_weights`
+const WEIGHTS_HEADER: &str = "byonn-weights v1";
+
+/// Learning rate as a function of the (0-based) epoch, used by `Network::fit_scheduled`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LrSchedule {
+    /// Keeps the initial rate
+    Constant,
+    /// Multiplies the rate by `gamma` once every `step_size` epochs
+    StepDecay { step_size: usize, gamma: f32 },
+    /// Multiplies the rate by `gamma` every epoch
+    ExponentialDecay { gamma: f32 },
+    /// eta_min + 0.5 * (initial - eta_min) * (1 + cos(pi * epoch / t_max)): falls smoothly
+    /// to `eta_min` at `t_max`, then climbs back, restarting every `2 * t_max` epochs
+    CosineAnnealing { t_max: usize, eta_min: f32 },
+}
+
+impl LrSchedule {
+    pub fn rate(&self, initial: f32, epoch: usize) -> f32 {
+        match *self {
+            LrSchedule::Constant => initial,
+            LrSchedule::StepDecay { step_size, gamma } => {
+                initial * gamma.powi((epoch / step_size.max(1)) as i32)
+            }
+            LrSchedule::ExponentialDecay { gamma } => initial * gamma.powi(epoch as i32),
+            LrSchedule::CosineAnnealing { t_max, eta_min } => {
+                let progress = epoch as f32 / t_max.max(1) as f32;
+                eta_min
+                    + 0.5 * (initial - eta_min) * (1.0 + (std::f32::consts::PI * progress).cos())
+            }
+        }
+    }
+}
+
+/// Summary of a `Network::fit_with_report` run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrainReport {
+    pub epochs: usize,
+    /// Loss of the last epoch's forward pass, NaN when no epoch ran
+    pub final_loss: f32,
+    pub elapsed: Duration,
+    /// Training rows processed per second, counting every epoch
+    pub samples_per_sec: f64,
+}
 
 pub struct Network {
     layers: Vec<Box<dyn Layer>>,
+    // One per layer, "layer_{index}" unless given through `add_named_layer`
+    names: Vec<String>,
     loss_grad_fn: LossGradFn,
+    // What the `fit_with_*` variants report, MSE unless set through `NetworkBuilder::loss`
+    loss_fn: LossFn,
+    // (weight, gradient) terms from `NetworkBuilder::add_loss`, added onto the main delta
+    extra_losses: Vec<(f32, LossGradFn)>,
+    // Layers before this index are left untouched by training
+    frozen: usize,
+    cache_frozen: bool,
+    // (input, output of the frozen prefix) from the last training step
+    frozen_cache: Option<(Tensor, Tensor)>,
 }
 
 impl Network {
@@ -26,6 +94,131 @@ impl Network {
         Ok(current_output)
     }
 
+    /// The loss gradient function given to `NetworkBuilder::loss_gradient`, for training
+    /// code outside `fit` that needs the same delta. Terms added with
+    /// `NetworkBuilder::add_loss` are not part of it, see `loss_delta`.
+    pub fn loss_grad_fn(&self) -> LossGradFn {
+        self.loss_grad_fn
+    }
+
+    /// The delta that training backpropagates: the main loss gradient plus the weighted
+    /// gradient of every loss from `NetworkBuilder::add_loss`, all against `actual`
+    pub fn loss_delta(&self, predicted: &Tensor, actual: &Tensor) -> Result<Tensor, TensorError> {
+        let mut delta = (self.loss_grad_fn)(predicted, actual)?;
+        for (weight, grad_fn) in &self.extra_losses {
+            delta = delta.add(&grad_fn(predicted, actual)?.scale(weight)?)?;
+        }
+        Ok(delta)
+    }
+
+    /// The loss reported by `fit_with_history` and the other reporting variants
+    fn reported_loss(&self, predicted: &Tensor, actual: &Tensor) -> Result<f32, TensorError> {
+        Ok((self.loss_fn)(predicted, actual)?.data()[0])
+    }
+
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// The `index`-th layer, for example to seed specific weights after building
+    /// through `Layer::set_params`
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut dyn Layer> {
+        // The caller may change a frozen layer, so its cached output can't be trusted
+        self.frozen_cache = None;
+        match self.layers.get_mut(index) {
+            Some(layer) => Some(layer.as_mut()),
+            None => None,
+        }
+    }
+
+    /// The first layer with the given name (`layer_{index}` for unnamed ones)
+    pub fn layer_by_name(&self, name: &str) -> Option<&dyn Layer> {
+        let index = self.names.iter().position(|n| n == name)?;
+        Some(self.layers[index].as_ref())
+    }
+
+    /// Stops training the layers before `index`, so only the head from `index` on learns.
+    /// `freeze_up_to(0)` unfreezes everything.
+    pub fn freeze_up_to(&mut self, index: usize) -> Result<(), TensorError> {
+        if index > self.layers.len() {
+            return Err(TensorError::IndexOutOfBounds);
+        }
+
+        self.frozen = index;
+        self.frozen_cache = None;
+        Ok(())
+    }
+
+    /// Whether training reuses the frozen prefix's output while the input stays the same
+    /// (on by default). Turning it off also drops the cached output.
+    pub fn set_frozen_cache(&mut self, enabled: bool) {
+        self.cache_frozen = enabled;
+        self.frozen_cache = None;
+    }
+
+    /// Total number of trainable values across all layers
+    pub fn num_parameters(&self) -> usize {
+        self.layers.iter().map(|l| l.num_params()).sum()
+    }
+
+    /// Parameters of every layer, in layer order
+    pub fn params(&self) -> Vec<&Tensor> {
+        self.layers.iter().flat_map(|l| l.params()).collect()
+    }
+
+    /// Shape of the network output for an input of `input_shape`, checked layer by layer
+    /// without running any data
+    pub fn output_shape(&self, input_shape: &[usize]) -> Result<Vec<usize>, TensorError> {
+        self.layers
+            .iter()
+            .try_fold(input_shape.to_vec(), |shape, layer| {
+                layer.output_shape(&shape)
+            })
+    }
+
+    /// Runs a forward pass without touching any layer state, so a trained
+    /// network can be shared behind an immutable reference for inference
+    pub fn predict(&self, input: &Tensor) -> Result<Tensor, TensorError> {
+        let mut current_output = input.clone();
+
+        for layer in &self.layers {
+            current_output = layer.forward_infer(&current_output)?;
+        }
+
+        Ok(current_output)
+    }
+
+    /// Raw network output of a classifier, one row of probabilities (or scores) per sample
+    pub fn predict_proba(&self, input: &Tensor) -> Result<Tensor, TensorError> {
+        self.predict(input)
+    }
+
+    /// Class index of every sample. A single output column is a binary classifier whose
+    /// class is 1 when the output reaches `threshold`; wider outputs pick the largest column
+    /// (the first one on ties) and ignore `threshold`.
+    pub fn predict_class(&self, input: &Tensor, threshold: f32) -> Result<Vec<usize>, TensorError> {
+        let proba = self.predict_proba(input)?;
+        if proba.shape().len() != 2 {
+            return Err(TensorError::InvalidRank);
+        }
+
+        let classes = proba
+            .rows()
+            .map(|row| match row {
+                [p] => (*p >= threshold) as usize,
+                _ => {
+                    row.iter()
+                        .enumerate()
+                        .fold((0, f32::NEG_INFINITY), |best, (i, &v)| {
+                            if v > best.1 { (i, v) } else { best }
+                        })
+                        .0
+                }
+            })
+            .collect();
+        Ok(classes)
+    }
+
     /// The training loop: Forward, Loss Gradient, and Backpropagation
     pub fn fit(
         &mut self,
@@ -33,56 +226,554 @@ impl Network {
         y_train: &Tensor,
         epochs: usize,
         learning_rate: f32,
+    ) -> Result<(), TensorError> {
+        for _ in 0..epochs {
+            self.train_step(x_train, y_train, learning_rate)?;
+        }
+        Ok(())
+    }
+
+    /// Same as `fit`, but times the run and reports the final loss and throughput
+    pub fn fit_with_report(
+        &mut self,
+        x_train: &Tensor,
+        y_train: &Tensor,
+        epochs: usize,
+        learning_rate: f32,
+    ) -> Result<TrainReport, TensorError> {
+        let start = Instant::now();
+
+        let mut final_loss = f32::NAN;
+        for epoch in 1..=epochs {
+            let output = self.train_step(x_train, y_train, learning_rate)?;
+
+            if epoch == epochs {
+                final_loss = self.reported_loss(&output, y_train)?;
+            }
+        }
+
+        let elapsed = start.elapsed();
+        let samples = x_train.shape().first().copied().unwrap_or(0) * epochs;
+
+        Ok(TrainReport {
+            epochs,
+            final_loss,
+            elapsed,
+            samples_per_sec: samples as f64 / elapsed.as_secs_f64(),
+        })
+    }
+
+    /// Shuffles the rows once, holds out `val_fraction` of them for validation and
+    /// trains on the rest. Returns the (train, validation) loss after every epoch.
+    pub fn fit_with_validation(
+        &mut self,
+        x: &Tensor,
+        y: &Tensor,
+        epochs: usize,
+        learning_rate: f32,
+        val_fraction: f32,
+        rng: &mut dyn Rng,
+    ) -> Result<Vec<(f32, f32)>, TensorError> {
+        let (x, y) = shuffle_rows(x, y, rng)?;
+
+        let rows = x.shape()[0];
+        let val_rows = (rows as f32 * val_fraction).round() as usize;
+
+        // Both splits need at least one row, otherwise one of the losses is undefined
+        if val_rows == 0 || val_rows >= rows {
+            return Err(TensorError::ShapeMismatch);
+        }
+
+        let (x_train, x_val) = split_rows(&x, rows - val_rows)?;
+        let (y_train, y_val) = split_rows(&y, rows - val_rows)?;
+
+        let mut history = Vec::with_capacity(epochs);
+        for _ in 0..epochs {
+            self.train_step(&x_train, &y_train, learning_rate)?;
+
+            let train_loss = self.reported_loss(&self.predict(&x_train)?, &y_train)?;
+            let val_loss = self.reported_loss(&self.predict(&x_val)?, &y_val)?;
+            history.push((train_loss, val_loss));
+        }
+        Ok(history)
+    }
+
+    /// Same as `fit`, but returns the loss of every epoch's forward pass.
+    /// Stops with `NumericalInstability` as soon as the loss turns into NaN.
+    pub fn fit_with_history(
+        &mut self,
+        x_train: &Tensor,
+        y_train: &Tensor,
+        epochs: usize,
+        learning_rate: f32,
+    ) -> Result<Vec<f32>, TensorError> {
+        let mut history = Vec::with_capacity(epochs);
+        for _ in 0..epochs {
+            let output = self.train_step(x_train, y_train, learning_rate)?;
+
+            let loss = self.reported_loss(&output, y_train)?;
+            if loss.is_nan() {
+                return Err(TensorError::NumericalInstability);
+            }
+            history.push(loss);
+        }
+        Ok(history)
+    }
+
+    /// Same as `fit`, but after every `every` completed epochs the callback receives
+    /// the epoch number (starting at 1) and the loss of that epoch's forward pass
+    pub fn fit_with_callback(
+        &mut self,
+        x_train: &Tensor,
+        y_train: &Tensor,
+        epochs: usize,
+        learning_rate: f32,
+        every: usize,
+        callback: &mut dyn FnMut(usize, f32),
+    ) -> Result<(), TensorError> {
+        for epoch in 1..=epochs {
+            let output = self.train_step(x_train, y_train, learning_rate)?;
+
+            if every > 0 && epoch % every == 0 {
+                let loss = self.reported_loss(&output, y_train)?;
+                callback(epoch, loss);
+            }
+        }
+        Ok(())
+    }
+
+    /// Same as `fit`, but the learning rate of every epoch comes from `schedule`
+    pub fn fit_scheduled(
+        &mut self,
+        x_train: &Tensor,
+        y_train: &Tensor,
+        epochs: usize,
+        initial_lr: f32,
+        schedule: LrSchedule,
     ) -> Result<(), TensorError> {
         for epoch in 0..epochs {
-            // Following is the forward pass
-            let input = Tensor::new(x_train.data().to_vec(), x_train.shape().to_vec())?;
-            let output = self.forward(input)?;
+            self.train_step(x_train, y_train, schedule.rate(initial_lr, epoch))?;
+        }
+        Ok(())
+    }
+
+    /// Same as `fit`, but every trainable layer (one with parameters) gets its own rate
+    /// from `lrs`, in layer order. Activations and other parameter-free layers are skipped.
+    pub fn fit_per_layer_lr(
+        &mut self,
+        x_train: &Tensor,
+        y_train: &Tensor,
+        epochs: usize,
+        lrs: &[f32],
+    ) -> Result<(), TensorError> {
+        let trainable = self.layers.iter().filter(|l| l.num_params() > 0).count();
+        if lrs.len() != trainable {
+            return Err(TensorError::ShapeMismatch);
+        }
 
-            // Loss gradient
-            let mut gradient = (self.loss_grad_fn)(&output, y_train)?;
+        // Spread the rates over all layers; the parameter-free ones never read theirs
+        let mut lrs = lrs.iter();
+        let rates: Vec<f32> = self
+            .layers
+            .iter()
+            .map(|l| {
+                if l.num_params() > 0 {
+                    *lrs.next().unwrap()
+                } else {
+                    0.0
+                }
+            })
+            .collect();
 
-            // Passing the gradient backward from output to input
-            for layer in self.layers.iter_mut().rev() {
-                gradient = layer.backward(&gradient, learning_rate)?;
+        for _ in 0..epochs {
+            self.train_step_with_rates(x_train, y_train, &rates)?;
+        }
+        Ok(())
+    }
+
+    /// Same as `fit`, but each epoch first computes every layer's gradients, rescales
+    /// them all by the same factor so their combined L2 norm is at most `max_norm`
+    /// (see `clip_grad_norm`), and only then updates the weights
+    pub fn fit_clipped(
+        &mut self,
+        x_train: &Tensor,
+        y_train: &Tensor,
+        epochs: usize,
+        learning_rate: f32,
+        max_norm: f32,
+    ) -> Result<(), TensorError> {
+        for _ in 0..epochs {
+            let output = self.forward_train(x_train)?;
+            if output.has_nan() {
+                return Err(TensorError::NumericalInstability);
+            }
+
+            let mut gradient = self.loss_delta(&output, y_train)?;
+            for layer in self.layers[self.frozen..].iter_mut().rev() {
+                gradient = layer.compute_gradients(&gradient)?;
+            }
+
+            self.clip_grad_norm(max_norm)?;
+
+            for layer in &mut self.layers[self.frozen..] {
+                layer.apply_gradients(learning_rate)?;
             }
         }
         Ok(())
     }
+
+    /// Combined L2 norm of the gradients stored in every layer that is not frozen
+    pub fn grad_norm(&self) -> f32 {
+        self.layers[self.frozen..]
+            .iter()
+            .flat_map(|l| l.grads())
+            .flat_map(|g| g.data())
+            .map(|v| v * v)
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    /// Scales all stored gradients uniformly so their combined norm does not exceed
+    /// `max_norm`. Returns the norm before clipping.
+    pub fn clip_grad_norm(&mut self, max_norm: f32) -> Result<f32, TensorError> {
+        let norm = self.grad_norm();
+        if norm > max_norm {
+            let factor = max_norm / norm;
+            for layer in &mut self.layers[self.frozen..] {
+                layer.scale_grads(factor)?;
+            }
+        }
+        Ok(norm)
+    }
+
+    /// Same as `fit`, but saves the weights to `path` after every `every` epochs
+    /// (and after the last one), so an interrupted run can resume from `load_weights`
+    pub fn fit_with_checkpoint(
+        &mut self,
+        x_train: &Tensor,
+        y_train: &Tensor,
+        epochs: usize,
+        learning_rate: f32,
+        every: usize,
+        path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        for epoch in 1..=epochs {
+            self.train_step(x_train, y_train, learning_rate)?;
+
+            if (every > 0 && epoch % every == 0) || epoch == epochs {
+                self.save_weights(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes every layer's parameters as text: a header, the layer count, then per layer
+    /// its tensor count followed by one shape line and one data line per tensor.
+    /// The file goes to a temporary name first, so a crash never leaves half a checkpoint.
+    pub fn save_weights(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let tmp_path = format!("{}.tmp", path);
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+
+        writeln!(writer, "{}", WEIGHTS_HEADER)?;
+        writeln!(writer, "{}", self.layers.len())?;
+        for layer in &self.layers {
+            let params = layer.params();
+            writeln!(writer, "{}", params.len())?;
+            for t in params {
+                let join = |v: Vec<String>| v.join(" ");
+                writeln!(
+                    writer,
+                    "{}",
+                    join(t.shape().iter().map(|d| d.to_string()).collect())
+                )?;
+                writeln!(
+                    writer,
+                    "{}",
+                    join(t.data().iter().map(|v| v.to_string()).collect())
+                )?;
+            }
+        }
+        writer.flush()?;
+        drop(writer);
+
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Loads weights written by `save_weights` into a network with the same architecture
+    pub fn load_weights(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let mut lines = content.lines();
+        let mut next_line = || lines.next().ok_or("Checkpoint ends early");
+
+        if next_line()? != WEIGHTS_HEADER {
+            return Err("Not a weights checkpoint".into());
+        }
+        let num_layers: usize = next_line()?.trim().parse()?;
+        if num_layers != self.layers.len() {
+            return Err(TensorError::ShapeMismatch.into());
+        }
+
+        // Parse everything first
+        let mut all_params = Vec::with_capacity(num_layers);
+        for _ in 0..num_layers {
+            let count: usize = next_line()?.trim().parse()?;
+            let mut params = Vec::with_capacity(count);
+            for _ in 0..count {
+                let shape = next_line()?
+                    .split_whitespace()
+                    .map(|d| d.parse::<usize>())
+                    .collect::<Result<Vec<usize>, _>>()?;
+                let data = next_line()?
+                    .split_whitespace()
+                    .map(|v| v.parse::<f32>())
+                    .collect::<Result<Vec<f32>, _>>()?;
+                params.push(Tensor::new(data, shape)?);
+            }
+            all_params.push(params);
+        }
+
+        // Then check every shape against the layers, so a bad file leaves them all untouched
+        for (layer, params) in self.layers.iter().zip(&all_params) {
+            let current = layer.params();
+            if current.len() != params.len()
+                || current
+                    .iter()
+                    .zip(params)
+                    .any(|(c, p)| c.shape() != p.shape())
+            {
+                return Err(TensorError::ShapeMismatch.into());
+            }
+        }
+
+        self.frozen_cache = None;
+        for (layer, params) in self.layers.iter_mut().zip(all_params) {
+            layer.set_params(params)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a loss curve (as returned by `fit_with_history`) with one value per line
+    pub fn save_history(history: &[f32], path: &str) -> Result<(), Box<dyn Error>> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        for loss in history {
+            writeln!(writer, "{}", loss)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a loss curve written by `save_history`, skipping blank lines
+    pub fn load_history(path: &str) -> Result<Vec<f32>, Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let history = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.trim().parse::<f32>())
+            .collect::<Result<Vec<f32>, _>>()?;
+        Ok(history)
+    }
+
+    /// One epoch: Forward, Loss Gradient, and Backpropagation.
+    /// Returns the output of the forward pass, computed before the weights were updated.
+    /// A NaN in that output means the weights already diverged, so training stops there.
+    fn train_step(
+        &mut self,
+        x_train: &Tensor,
+        y_train: &Tensor,
+        learning_rate: f32,
+    ) -> Result<Tensor, TensorError> {
+        let rates = vec![learning_rate; self.layers.len()];
+        self.train_step_with_rates(x_train, y_train, &rates)
+    }
+
+    /// `train_step` with one learning rate per layer
+    fn train_step_with_rates(
+        &mut self,
+        x_train: &Tensor,
+        y_train: &Tensor,
+        rates: &[f32],
+    ) -> Result<Tensor, TensorError> {
+        // Following is the forward pass
+        let output = self.forward_train(x_train)?;
+
+        if output.has_nan() {
+            return Err(TensorError::NumericalInstability);
+        }
+
+        // Loss gradient
+        let mut gradient = self.loss_delta(&output, y_train)?;
+
+        // Passing the gradient backward from output to input, stopping at the frozen layers
+        for (layer, &rate) in self.layers.iter_mut().zip(rates).skip(self.frozen).rev() {
+            gradient = layer.backward(&gradient, rate)?;
+        }
+        Ok(output)
+    }
+
+    /// Forward pass for training: the frozen prefix only runs inference (its output comes
+    /// from the cache when the input is unchanged) and the remaining layers keep what
+    /// their backward pass needs
+    fn forward_train(&mut self, x_train: &Tensor) -> Result<Tensor, TensorError> {
+        let mut current_output = match &self.frozen_cache {
+            Some((input, output)) if self.cache_frozen && input == x_train => output.clone(),
+            _ => {
+                let mut output = x_train.clone();
+                for layer in &self.layers[..self.frozen] {
+                    output = layer.forward_infer(&output)?;
+                }
+                if self.cache_frozen && self.frozen > 0 {
+                    self.frozen_cache = Some((x_train.clone(), output.clone()));
+                }
+                output
+            }
+        };
+
+        for layer in &mut self.layers[self.frozen..] {
+            current_output = layer.forward(&current_output)?;
+        }
+        Ok(current_output)
+    }
 }
 
 /// Builder pattern for cleaner Network initialization
 pub struct NetworkBuilder {
     layers: Vec<Box<dyn Layer>>,
+    names: Vec<String>,
     loss_grad: Option<LossGradFn>,
+    loss: Option<LossFn>,
+    extra_losses: Vec<(f32, LossGradFn)>,
+}
+
+impl Default for NetworkBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl NetworkBuilder {
     pub fn new() -> Self {
         Self {
             layers: Vec::new(),
+            names: Vec::new(),
             loss_grad: None,
+            loss: None,
+            extra_losses: Vec::new(),
+        }
+    }
+
+    /// Starts a builder with one `dense` block per `(width, activation)` entry, each
+    /// taking the previous width as input and the first one taking `input_dim`
+    pub fn from_spec(
+        spec: &[(usize, ActivationType)],
+        input_dim: usize,
+        rng: &mut dyn Rng,
+    ) -> Self {
+        Self::from_spec_with_init(spec, input_dim, InitScheme::Xavier, rng)
+    }
+
+    /// Same as `from_spec`, but every block uses `dense_with_init` with the given scheme
+    pub fn from_spec_with_init(
+        spec: &[(usize, ActivationType)],
+        input_dim: usize,
+        init: InitScheme,
+        rng: &mut dyn Rng,
+    ) -> Self {
+        let mut builder = Self::new();
+        let mut in_features = input_dim;
+        for &(width, activation) in spec {
+            builder = builder.dense_with_init(in_features, width, activation, init, rng);
+            in_features = width;
         }
+        builder
     }
 
     /// Adds a layer to the network stack
-    pub fn add_layer(mut self, layer: Box<dyn Layer>) -> Self {
+    pub fn add_layer(self, layer: Box<dyn Layer>) -> Self {
+        let name = format!("layer_{}", self.layers.len());
+        self.add_named_layer(&name, layer)
+    }
+
+    /// Same as `add_layer`, but the layer can later be found with `Network::layer_by_name`
+    pub fn add_named_layer(mut self, name: &str, layer: Box<dyn Layer>) -> Self {
         self.layers.push(layer);
+        self.names.push(name.to_string());
         self
     }
 
+    /// Adds a Xavier-initialized Linear layer followed by its activation
+    pub fn dense(
+        self,
+        in_features: usize,
+        out_features: usize,
+        activation: ActivationType,
+        rng: &mut dyn Rng,
+    ) -> Self {
+        self.dense_with_init(
+            in_features,
+            out_features,
+            activation,
+            InitScheme::Xavier,
+            rng,
+        )
+    }
+
+    /// Same as `dense`, but with an explicit weight initialization scheme
+    pub fn dense_with_init(
+        self,
+        in_features: usize,
+        out_features: usize,
+        activation: ActivationType,
+        init: InitScheme,
+        rng: &mut dyn Rng,
+    ) -> Self {
+        self.add_layer(Box::new(Linear::with_init(
+            in_features,
+            out_features,
+            init,
+            rng,
+        )))
+        .add_layer(Box::new(Activation::new(activation)))
+    }
+
     /// Injects the loss gradient function from loss.rs
     pub fn loss_gradient(mut self, f: LossGradFn) -> Self {
         self.loss_grad = Some(f);
         self
     }
 
+    /// The loss that the `fit_with_*` variants report, matching `loss_gradient`.
+    /// Defaults to `mse_loss` when not set.
+    pub fn loss(mut self, f: LossFn) -> Self {
+        self.loss = Some(f);
+        self
+    }
+
+    /// Adds `weight` times another loss gradient onto the one from `loss_gradient`,
+    /// so training follows the weighted sum of the losses
+    pub fn add_loss(mut self, weight: f32, f: LossGradFn) -> Self {
+        self.extra_losses.push((weight, f));
+        self
+    }
+
+    /// Fails when no loss gradient was set or no layer was added
     pub fn build(self) -> Result<Network, String> {
         let loss_grad_fn = self.loss_grad.ok_or("Loss gradient function is required")?;
-        
+
+        // An empty network would silently pass its input through and train nothing
+        if self.layers.is_empty() {
+            return Err("At least one layer is required".to_string());
+        }
+
         Ok(Network {
             layers: self.layers,
+            names: self.names,
             loss_grad_fn,
+            loss_fn: self.loss.unwrap_or(mse_loss),
+            extra_losses: self.extra_losses,
+            frozen: 0,
+            cache_frozen: true,
+            frozen_cache: None,
         })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,72 @@
+use crate::Layer;
+use crate::tensor::Tensor;
+use crate::tensor::TensorError;
+
+/// Skip connection around a layer: output = inner(x) + x.
+/// The inner layer must keep the shape of its input.
+pub struct Residual {
+    inner: Box<dyn Layer>,
+}
+
+impl Layer for Residual {
+    fn forward(&mut self, input: &Tensor) -> Result<Tensor, TensorError> {
+        self.inner.forward(input)?.add(input)
+    }
+
+    fn forward_infer(&self, input: &Tensor) -> Result<Tensor, TensorError> {
+        self.inner.forward_infer(input)?.add(input)
+    }
+
+    fn backward(
+        &mut self,
+        output_error: &Tensor,
+        learning_rate: f32,
+    ) -> Result<Tensor, TensorError> {
+        // The error reaches the input through the inner layer and, unchanged, through the skip
+        let inner_error = self.inner.backward(output_error, learning_rate)?;
+        inner_error.add(output_error)
+    }
+
+    fn compute_gradients(&mut self, output_error: &Tensor) -> Result<Tensor, TensorError> {
+        let inner_error = self.inner.compute_gradients(output_error)?;
+        inner_error.add(output_error)
+    }
+
+    fn grads(&self) -> Vec<&Tensor> {
+        self.inner.grads()
+    }
+
+    fn scale_grads(&mut self, factor: f32) -> Result<(), TensorError> {
+        self.inner.scale_grads(factor)
+    }
+
+    fn apply_gradients(&mut self, learning_rate: f32) -> Result<(), TensorError> {
+        self.inner.apply_gradients(learning_rate)
+    }
+
+    fn num_params(&self) -> usize {
+        self.inner.num_params()
+    }
+
+    fn params(&self) -> Vec<&Tensor> {
+        self.inner.params()
+    }
+
+    fn set_params(&mut self, params: Vec<Tensor>) -> Result<(), TensorError> {
+        self.inner.set_params(params)
+    }
+
+    fn output_shape(&self, input_shape: &[usize]) -> Result<Vec<usize>, TensorError> {
+        let inner_shape = self.inner.output_shape(input_shape)?;
+        if inner_shape != input_shape {
+            return Err(TensorError::ShapeMismatch);
+        }
+        Ok(inner_shape)
+    }
+}
+
+impl Residual {
+    pub fn new(inner: Box<dyn Layer>) -> Self {
+        Residual { inner }
+    }
+}
@@ -1,10 +1,15 @@
 use std::error::Error;
+use std::ops::{Add, AddAssign, Div, Mul, Sub};
 
-#[derive(Debug, PartialEq)]
+use crate::image_utils::{read_pbm, save_as_pbm_with_threshold};
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum TensorError {
     ShapeMismatch,
     InvalidRank,
     InconsistentData,
+    IndexOutOfBounds,
+    NumericalInstability,
 }
 
 impl Error for TensorError {}
@@ -15,19 +20,104 @@ impl std::fmt::Display for TensorError {
             TensorError::ShapeMismatch => {
                 write!(f, "Tensor shapes do not match for the operation.")
             }
-            TensorError::InvalidRank => write!(f, "Tensor rank is invalid (must be 1D or 2D)."),
+            TensorError::InvalidRank => {
+                write!(f, "Tensor rank is invalid (must be 1D to {}D).", MAX_RANK)
+            }
             TensorError::InconsistentData => write!(f, "Data length does not match tensor shape."),
+            TensorError::IndexOutOfBounds => write!(f, "Index is outside the tensor bounds."),
+            TensorError::NumericalInstability => write!(
+                f,
+                "Values became NaN during training, try a lower learning rate."
+            ),
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct Tensor {
-    data: Vec<f32>,
+/// Highest rank a tensor may have. Rank 3 is only used for batches of
+/// matrices, most operations still expect 1D or 2D input.
+pub const MAX_RANK: usize = 3;
+
+/// Whether `matmul` cross-checks its result against `matmul_naive`, only in debug builds
+const MATMUL_CROSS_CHECK: bool = cfg!(debug_assertions);
+/// Largest product size (output elements x inner dimension) `matmul` cross-checks,
+/// so debug runs of the big examples don't pay for a second, naive multiplication
+const MATMUL_CROSS_CHECK_MAX_WORK: usize = 64 * 64 * 64;
+
+/// First bytes of every NumPy `.npy` file
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Tile edge used by the cache-blocked transpose
+const TRANSPOSE_BLOCK: usize = 32;
+/// Element count from which `transpose` switches to the blocked version
+const BLOCKED_TRANSPOSE_THRESHOLD: usize = 64 * 64;
+
+/// Number of `Tensor::new` calls, only tracked with the `alloc-counter` feature
+#[cfg(feature = "alloc-counter")]
+static ALLOCATIONS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Tensors built through `Tensor::new` since the start (or the last reset).
+/// Always 0 without the `alloc-counter` feature.
+pub fn allocation_count() -> usize {
+    #[cfg(feature = "alloc-counter")]
+    return ALLOCATIONS.load(std::sync::atomic::Ordering::Relaxed);
+
+    #[cfg(not(feature = "alloc-counter"))]
+    0
+}
+
+pub fn reset_allocation_count() {
+    #[cfg(feature = "alloc-counter")]
+    ALLOCATIONS.store(0, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Element type a tensor can hold. Covers the arithmetic the generic tensor operations
+/// need, so `f64` tensors can be used where `f32` accumulation loses too much precision
+pub trait Float:
+    Copy
+    + PartialEq
+    + PartialOrd
+    + std::fmt::Debug
+    + std::fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + AddAssign
+{
+    const ZERO: Self;
+
+    fn exp(self) -> Self;
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+
+    fn exp(self) -> Self {
+        f32::exp(self)
+    }
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TensorOf<T> {
+    data: Vec<T>,
     shape: Vec<usize>,
 }
 
-impl std::fmt::Display for Tensor {
+pub type TensorF32 = TensorOf<f32>;
+pub type TensorF64 = TensorOf<f64>;
+
+/// The tensor used throughout the network code
+pub type Tensor = TensorF32;
+
+impl<T: Float> std::fmt::Display for TensorOf<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Use default debug format for 1D vectors
         if self.shape.len() != 2 {
@@ -53,57 +143,129 @@ impl std::fmt::Display for Tensor {
     }
 }
 
-impl Tensor {
-    fn _element_wise_op(
-        &self,
-        other: &Tensor,
-        op: impl Fn(f32, f32) -> f32,
-    ) -> Result<Tensor, TensorError> {
+/// Operations available for every element type
+impl<T: Float> TensorOf<T> {
+    fn _element_wise_op(&self, other: &Self, op: impl Fn(T, T) -> T) -> Result<Self, TensorError> {
         if self.shape != other.shape {
             return Err(TensorError::ShapeMismatch);
         }
 
-        let data: Vec<f32> = self
+        let data: Vec<T> = self
             .data
             .iter()
             .zip(other.data.iter())
             .map(|(&a, &b)| op(a, b))
             .collect();
 
-        Tensor::new(data, self.shape.clone())
+        Self::new(data, self.shape.clone())
     }
 
-    pub fn clone(&self) -> Tensor {
-        Self {
-            data: self.data().to_vec(),
-            shape: self.shape().to_vec(),
-        }
-    }
-
-    fn _element_wise_op_single<F>(&self, op: F) -> Result<Tensor, TensorError>
+    fn _element_wise_op_single<F>(&self, op: F) -> Result<Self, TensorError>
     where
-        F: Fn(f32) -> f32,
+        F: Fn(T) -> T,
     {
         let mut new_data = Vec::with_capacity(self.data.len());
         for &val in &self.data {
             new_data.push(op(val));
         }
-        Tensor::new(new_data, self.shape.clone())
+        Self::new(new_data, self.shape.clone())
     }
 
-    pub fn new(data: Vec<f32>, shape: Vec<usize>) -> Result<Tensor, TensorError> {
-        if shape.len() == 0 || shape.len() > 2 {
+    pub fn new(data: Vec<T>, shape: Vec<usize>) -> Result<Self, TensorError> {
+        if shape.is_empty() || shape.len() > MAX_RANK {
             return Err(TensorError::InvalidRank);
         }
 
         if data.len() != shape.iter().product::<usize>() {
             return Err(TensorError::InconsistentData);
         }
-        Ok(Tensor { data, shape })
+
+        #[cfg(feature = "alloc-counter")]
+        ALLOCATIONS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+        Ok(TensorOf { data, shape })
+    }
+
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    pub fn add(&self, other: &Self) -> Result<Self, TensorError> {
+        self._element_wise_op(other, |a, b| a + b)
+    }
+
+    pub fn sub(&self, other: &Self) -> Result<Self, TensorError> {
+        self._element_wise_op(other, |a, b| a - b)
+    }
+
+    pub fn mul(&self, other: &Self) -> Result<Self, TensorError> {
+        self._element_wise_op(other, |a, b| a * b)
+    }
+
+    pub fn div(&self, other: &Self) -> Result<Self, TensorError> {
+        self._element_wise_op(other, |a, b| a / b)
+    }
+
+    /// Applies `f` to every element, keeping the shape
+    pub fn map<F: Fn(T) -> T>(&self, f: F) -> Result<Self, TensorError> {
+        self._element_wise_op_single(f)
+    }
+
+    /// Combines two tensors of equal shape element by element with `f`
+    pub fn zip_map<F: Fn(T, T) -> T>(&self, other: &Self, f: F) -> Result<Self, TensorError> {
+        self._element_wise_op(other, f)
     }
 
+    pub fn exp(&self) -> Result<Self, TensorError> {
+        self._element_wise_op_single(T::exp)
+    }
+
+    pub fn matmul_naive(&self, other: &Self) -> Result<Self, TensorError> {
+        let (a_rows, a_cols) = match self.shape.len() {
+            1 => (1, self.shape[0]),
+            2 => (self.shape[0], self.shape[1]),
+            _ => return Err(TensorError::InvalidRank),
+        };
+
+        let (b_rows, b_cols) = match other.shape.len() {
+            1 => (other.shape[0], 1),
+            2 => (other.shape[0], other.shape[1]),
+            _ => return Err(TensorError::InvalidRank),
+        };
+
+        if a_cols != b_rows {
+            return Err(TensorError::ShapeMismatch);
+        }
+
+        let mut result_data = vec![T::ZERO; a_rows * b_cols];
+
+        for i in 0..a_rows {
+            for j in 0..b_cols {
+                for k in 0..a_cols {
+                    result_data[i * b_cols + j] +=
+                        self.data[i * a_cols + k] * other.data[k * b_cols + j];
+                }
+            }
+        }
+
+        let out_shape = match (self.shape.len(), other.shape.len()) {
+            (1, 1) => vec![1],
+            (1, 2) => vec![b_cols],
+            (2, 1) => vec![a_rows],
+            _ => vec![a_rows, b_cols],
+        };
+
+        Self::new(result_data, out_shape)
+    }
+}
+
+impl Tensor {
     pub fn one(shape: Vec<usize>) -> Result<Tensor, TensorError> {
-        if shape.len() == 0 || shape.len() > 2 {
+        if shape.is_empty() || shape.len() > MAX_RANK {
             return Err(TensorError::InvalidRank);
         }
 
@@ -112,6 +274,31 @@ impl Tensor {
         Ok(Tensor { data, shape })
     }
 
+    /// Stacks identically shaped tensors along a new leading (batch) dimension,
+    /// e.g. N `[rows, cols]` matrices become one `[N, rows, cols]` tensor
+    pub fn stack(tensors: &[&Tensor]) -> Result<Tensor, TensorError> {
+        let first = tensors.first().ok_or(TensorError::ShapeMismatch)?;
+
+        if first.shape.len() + 1 > MAX_RANK {
+            return Err(TensorError::InvalidRank);
+        }
+
+        if tensors.iter().any(|t| t.shape != first.shape) {
+            return Err(TensorError::ShapeMismatch);
+        }
+
+        let data = tensors
+            .iter()
+            .flat_map(|t| t.data.iter().copied())
+            .collect();
+
+        let mut shape = Vec::with_capacity(first.shape.len() + 1);
+        shape.push(tensors.len());
+        shape.extend_from_slice(&first.shape);
+
+        Tensor::new(data, shape)
+    }
+
     pub fn empty() -> Tensor {
         Tensor {
             data: vec![],
@@ -119,28 +306,180 @@ impl Tensor {
         }
     }
 
-    pub fn data(&self) -> &[f32] {
-        &self.data
+    /// Overwrites every element with `value` in place, keeping the shape and the allocation
+    pub fn fill(&mut self, value: f32) {
+        self.data.fill(value);
     }
 
-    pub fn shape(&self) -> &[usize] {
-        &self.shape
+    /// Shorthand for `fill(0.0)`, for resetting scratch buffers and accumulators
+    pub fn zero_(&mut self) {
+        self.fill(0.0);
     }
 
-    pub fn add(&self, other: &Tensor) -> Result<Tensor, TensorError> {
-        self._element_wise_op(other, |a, b| a + b)
+    /// Loads a plain PBM image as an [h, w] tensor of 0/1 pixels
+    pub fn from_pbm(path: &str) -> Result<Tensor, Box<dyn Error>> {
+        let (w, h, pixels) = read_pbm(path)?;
+        Ok(Tensor::new(pixels, vec![h, w])?)
     }
 
-    pub fn sub(&self, other: &Tensor) -> Result<Tensor, TensorError> {
-        self._element_wise_op(other, |a, b| a - b)
+    /// Saves a 2D tensor as a plain PBM image, values at or above `threshold` become 1
+    pub fn to_pbm(&self, path: &str, threshold: f32) -> Result<(), Box<dyn Error>> {
+        let [h, w] = self.shape[..] else {
+            return Err(TensorError::InvalidRank.into());
+        };
+        save_as_pbm_with_threshold(path, w, h, &self.data, threshold, true)?;
+        Ok(())
     }
 
-    pub fn mul(&self, other: &Tensor) -> Result<Tensor, TensorError> {
-        self._element_wise_op(other, |a, b| a * b)
+    /// Saves the tensor as a NumPy `.npy` v1.0 file: little-endian f32, C order
+    pub fn save_npy(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let dims: Vec<String> = self.shape.iter().map(|d| d.to_string()).collect();
+        // A one-element tuple needs its trailing comma in Python syntax
+        let shape = match dims.len() {
+            1 => format!("({},)", dims[0]),
+            _ => format!("({})", dims.join(", ")),
+        };
+        let mut header = format!(
+            "{{'descr': '<f4', 'fortran_order': False, 'shape': {}, }}",
+            shape
+        );
+
+        // Magic, version and length take 10 bytes; the whole preamble is padded
+        // with spaces to a multiple of 64 and ends with a newline
+        let unpadded = NPY_MAGIC.len() + 4 + header.len() + 1;
+        header.push_str(&" ".repeat(unpadded.next_multiple_of(64) - unpadded));
+        header.push('\n');
+
+        let mut bytes = Vec::with_capacity(10 + header.len() + self.data.len() * 4);
+        bytes.extend_from_slice(NPY_MAGIC);
+        bytes.extend_from_slice(&[1, 0]);
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        for v in &self.data {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+
+        std::fs::write(path, bytes)?;
+        Ok(())
     }
 
-    pub fn div(&self, other: &Tensor) -> Result<Tensor, TensorError> {
-        self._element_wise_op(other, |a, b| a / b)
+    /// Loads a `.npy` v1.0 file holding little-endian f32 values in C order
+    pub fn load_npy(path: &str) -> Result<Tensor, Box<dyn Error>> {
+        let bytes = std::fs::read(path)?;
+
+        if bytes.len() < 10 || &bytes[..6] != NPY_MAGIC {
+            return Err("Not a .npy file".into());
+        }
+        if bytes[6] != 1 {
+            return Err("Only .npy version 1.x is supported".into());
+        }
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        let header = bytes
+            .get(10..10 + header_len)
+            .ok_or("Truncated .npy header")?;
+        let header = std::str::from_utf8(header)?;
+
+        if !header.contains("'descr': '<f4'") {
+            return Err("Only little-endian f32 ('<f4') data is supported".into());
+        }
+        if !header.contains("'fortran_order': False") {
+            return Err("Only C-ordered data is supported".into());
+        }
+
+        let shape_start = header.find("'shape': (").ok_or("Missing shape")? + "'shape': (".len();
+        let shape_len = header[shape_start..].find(')').ok_or("Missing shape")?;
+        let shape = header[shape_start..shape_start + shape_len]
+            .split(',')
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(|d| d.parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        let data = bytes[10 + header_len..]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+
+        Ok(Tensor::new(data, shape)?)
+    }
+
+    /// Maps a (row, col) pair to the flat index, treating a 1D tensor as a single row
+    fn _flat_index(&self, r: usize, c: usize) -> Option<usize> {
+        let (rows, cols) = match self.shape.as_slice() {
+            [c] => (1, *c),
+            [r, c] => (*r, *c),
+            _ => return None,
+        };
+
+        if r >= rows || c >= cols {
+            return None;
+        }
+
+        Some(r * cols + c)
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> Option<f32> {
+        self._flat_index(r, c).map(|i| self.data[i])
+    }
+
+    /// Iterates over the rows as slices. A row runs along the last dimension, so a 1D
+    /// tensor is a single row and a `[b, r, c]` batch yields `b * r` rows.
+    pub fn rows(&self) -> impl Iterator<Item = &[f32]> {
+        let (count, len) = self._row_layout();
+        (0..count).map(move |r| &self.data[r * len..(r + 1) * len])
+    }
+
+    pub fn row(&self, i: usize) -> Option<&[f32]> {
+        let (count, len) = self._row_layout();
+        (i < count).then(|| &self.data[i * len..(i + 1) * len])
+    }
+
+    /// (number of rows, row length) as used by `rows` and `row`
+    fn _row_layout(&self) -> (usize, usize) {
+        match self.shape.split_last() {
+            Some((&len, outer)) => (outer.iter().product(), len),
+            None => (0, 0),
+        }
+    }
+
+    pub fn has_nan(&self) -> bool {
+        self.data.iter().any(|v| v.is_nan())
+    }
+
+    pub fn get_flat(&self, i: usize) -> Option<f32> {
+        self.data.get(i).copied()
+    }
+
+    pub fn set(&mut self, r: usize, c: usize, v: f32) -> Result<(), TensorError> {
+        let i = self._flat_index(r, c).ok_or(TensorError::IndexOutOfBounds)?;
+        self.data[i] = v;
+        Ok(())
+    }
+
+    /// 1.0 where self > other, 0.0 elsewhere
+    pub fn gt(&self, other: &Tensor) -> Result<Tensor, TensorError> {
+        self._element_wise_op(other, |a, b| (a > b) as u8 as f32)
+    }
+
+    /// 1.0 where self >= other, 0.0 elsewhere
+    pub fn ge(&self, other: &Tensor) -> Result<Tensor, TensorError> {
+        self._element_wise_op(other, |a, b| (a >= b) as u8 as f32)
+    }
+
+    /// 1.0 where self < other, 0.0 elsewhere
+    pub fn lt(&self, other: &Tensor) -> Result<Tensor, TensorError> {
+        self._element_wise_op(other, |a, b| (a < b) as u8 as f32)
+    }
+
+    /// 1.0 where self <= other, 0.0 elsewhere
+    pub fn le(&self, other: &Tensor) -> Result<Tensor, TensorError> {
+        self._element_wise_op(other, |a, b| (a <= b) as u8 as f32)
+    }
+
+    /// 1.0 where self == other, 0.0 elsewhere. Shadows `PartialEq::eq` as a method,
+    /// use `==` to compare whole tensors.
+    pub fn eq(&self, other: &Tensor) -> Result<Tensor, TensorError> {
+        self._element_wise_op(other, |a, b| (a == b) as u8 as f32)
     }
 
     pub fn abs(&self) -> Result<Tensor, TensorError> {
@@ -151,11 +490,30 @@ impl Tensor {
         self._element_wise_op_single(|a: f32| a.powf(power))
     }
 
+    /// x * x for every element. Unlike `powf`, this never goes through a general power,
+    /// so it stays exact and cannot produce NaN from a negative base.
+    pub fn square(&self) -> Result<Tensor, TensorError> {
+        self._element_wise_op_single(|a| a * a)
+    }
+
     pub fn scale(&self, scalar: &f32) -> Result<Tensor, TensorError> {
         let scalar = *scalar;
         self._element_wise_op_single(|a: f32| a * scalar)
     }
 
+    pub fn add_scalar(&self, s: f32) -> Result<Tensor, TensorError> {
+        self._element_wise_op_single(|a| a + s)
+    }
+
+    pub fn sub_scalar(&self, s: f32) -> Result<Tensor, TensorError> {
+        self._element_wise_op_single(|a| a - s)
+    }
+
+    /// s - x for every element, e.g. `rsub_scalar(1.0)` for 1 - sigmoid
+    pub fn rsub_scalar(&self, s: f32) -> Result<Tensor, TensorError> {
+        self._element_wise_op_single(|a| s - a)
+    }
+
     pub fn relu(&self) -> Result<Tensor, TensorError> {
         self._element_wise_op_single(|a| if a > 0.0 { a } else { 0.0 })
     }
@@ -164,11 +522,38 @@ impl Tensor {
         self._element_wise_op_single(|a| if a > 0.0 { 1.0 } else { 0.0 })
     }
 
-    pub fn exp(&self) -> Result<Tensor, TensorError> {
-        self._element_wise_op_single(|a| f32::exp(a))
+    /// ln(1 + exp(x)), computed as max(x, 0) + ln(1 + exp(-|x|)) so large inputs don't overflow
+    pub fn softplus(&self) -> Result<Tensor, TensorError> {
+        self._element_wise_op_single(|a| a.max(0.0) + (-a.abs()).exp().ln_1p())
+    }
+
+    /// 1 / (1 + exp(-x)), switching to exp(x) / (1 + exp(x)) for negative x so that
+    /// large logits of either sign cannot overflow
+    pub fn sigmoid(&self) -> Result<Tensor, TensorError> {
+        self._element_wise_op_single(|a| {
+            if a >= 0.0 {
+                1.0 / (1.0 + (-a).exp())
+            } else {
+                let e = a.exp();
+                e / (1.0 + e)
+            }
+        })
+    }
+
+    pub fn tanh(&self) -> Result<Tensor, TensorError> {
+        self._element_wise_op_single(f32::tanh)
     }
 
     pub fn transpose(&self) -> Result<Tensor, TensorError> {
+        // Small matrices fit in cache anyway, so tiling only adds loop overhead
+        if self.data.len() >= BLOCKED_TRANSPOSE_THRESHOLD {
+            self.transpose_blocked()
+        } else {
+            self.transpose_naive()
+        }
+    }
+
+    pub fn transpose_naive(&self) -> Result<Tensor, TensorError> {
         if self.shape.len() != 1 && self.shape.len() != 2 {
             return Err(TensorError::InvalidRank);
         }
@@ -190,45 +575,360 @@ impl Tensor {
         Tensor::new(transposed_data, vec![cols, rows])
     }
 
-    pub fn matmul_naive(&self, other: &Tensor) -> Result<Tensor, TensorError> {
-        let (a_rows, a_cols) = match self.shape.len() {
-            1 => (1, self.shape[0]),
-            2 => (self.shape[0], self.shape[1]),
+    /// Transposes tile by tile so both the reads and the scattered writes stay within cache
+    pub fn transpose_blocked(&self) -> Result<Tensor, TensorError> {
+        let (rows, cols) = match self.shape.as_slice() {
+            [_] => return Tensor::new(self.data.clone(), self.shape.clone()),
+            [r, c] => (*r, *c),
             _ => return Err(TensorError::InvalidRank),
         };
 
-        let (b_rows, b_cols) = match other.shape.len() {
-            1 => (other.shape[0], 1),
-            2 => (other.shape[0], other.shape[1]),
-            _ => return Err(TensorError::InvalidRank),
+        let mut transposed_data = vec![0.0; self.data.len()];
+
+        for row_start in (0..rows).step_by(TRANSPOSE_BLOCK) {
+            let row_end = (row_start + TRANSPOSE_BLOCK).min(rows);
+            for col_start in (0..cols).step_by(TRANSPOSE_BLOCK) {
+                let col_end = (col_start + TRANSPOSE_BLOCK).min(cols);
+
+                for row in row_start..row_end {
+                    for col in col_start..col_end {
+                        transposed_data[col * rows + row] = self.data[row * cols + col];
+                    }
+                }
+            }
+        }
+
+        Tensor::new(transposed_data, vec![cols, rows])
+    }
+
+    /// Copies columns `[start, end)` of a 2D tensor into a new contiguous matrix
+    pub fn slice_cols(&self, start: usize, end: usize) -> Result<Tensor, TensorError> {
+        let [rows, cols] = self.shape[..] else {
+            return Err(TensorError::InvalidRank);
         };
 
-        if a_cols != b_rows {
+        if start > end || end > cols {
+            return Err(TensorError::IndexOutOfBounds);
+        }
+
+        let mut data = Vec::with_capacity(rows * (end - start));
+        for r in 0..rows {
+            data.extend_from_slice(&self.data[r * cols + start..r * cols + end]);
+        }
+
+        Tensor::new(data, vec![rows, end - start])
+    }
+
+    /// Surrounds a 2D tensor with borders of the given widths, all filled with `value`
+    pub fn pad(
+        &self,
+        top: usize,
+        bottom: usize,
+        left: usize,
+        right: usize,
+        value: f32,
+    ) -> Result<Tensor, TensorError> {
+        let [rows, cols] = self.shape[..] else {
+            return Err(TensorError::InvalidRank);
+        };
+
+        let out_rows = top + rows + bottom;
+        let out_cols = left + cols + right;
+        let mut padded = vec![value; out_rows * out_cols];
+
+        for r in 0..rows {
+            let start = (top + r) * out_cols + left;
+            padded[start..start + cols].copy_from_slice(&self.data[r * cols..(r + 1) * cols]);
+        }
+
+        Tensor::new(padded, vec![out_rows, out_cols])
+    }
+
+    /// Copies the data into a 1D `[n]` tensor, keeping the row-major order
+    pub fn flatten(&self) -> Tensor {
+        Self {
+            data: self.data.clone(),
+            shape: vec![self.data.len()],
+        }
+    }
+
+    /// Copies the data under `target_shape` when the two shapes only differ by dimensions
+    /// of size 1, so `[n]`, `[n, 1]` and `[1, n]` can be used interchangeably.
+    /// Anything that would actually reorder or regroup elements is a `ShapeMismatch`.
+    pub fn reshape_compatible(&self, target_shape: &[usize]) -> Result<Tensor, TensorError> {
+        let significant =
+            |shape: &[usize]| -> Vec<usize> { shape.iter().copied().filter(|&d| d != 1).collect() };
+
+        if significant(&self.shape) != significant(target_shape) {
             return Err(TensorError::ShapeMismatch);
         }
 
-        let mut result_data = vec![0.0; a_rows * b_cols];
+        Tensor::new(self.data.clone(), target_shape.to_vec())
+    }
 
-        for i in 0..a_rows {
-            for j in 0..b_cols {
-                for k in 0..a_cols {
-                    result_data[i * b_cols + j] +=
-                        self.data[i * a_cols + k] * other.data[k * b_cols + j];
+    /// Mirrors a 2D `[h, w]` image left to right by reversing every row
+    pub fn flip_horizontal(&self) -> Result<Tensor, TensorError> {
+        let [_, cols] = self.shape[..] else {
+            return Err(TensorError::InvalidRank);
+        };
+
+        let data = self
+            .data
+            .chunks(cols.max(1))
+            .flat_map(|row| row.iter().rev().copied())
+            .collect();
+
+        Tensor::new(data, self.shape.clone())
+    }
+
+    /// Mirrors a 2D `[h, w]` image top to bottom by reversing the order of the rows
+    pub fn flip_vertical(&self) -> Result<Tensor, TensorError> {
+        let [_, cols] = self.shape[..] else {
+            return Err(TensorError::InvalidRank);
+        };
+
+        let data = self.data.rchunks(cols.max(1)).flatten().copied().collect();
+
+        Tensor::new(data, self.shape.clone())
+    }
+
+    /// Repeats a 2D tensor block-wise, `reps_rows` times downwards and `reps_cols` times across
+    pub fn tile(&self, reps_rows: usize, reps_cols: usize) -> Result<Tensor, TensorError> {
+        let [rows, cols] = self.shape[..] else {
+            return Err(TensorError::InvalidRank);
+        };
+
+        let out_cols = cols * reps_cols;
+        let mut data = Vec::with_capacity(rows * reps_rows * out_cols);
+        for _ in 0..reps_rows {
+            for r in 0..rows {
+                let row = &self.data[r * cols..(r + 1) * cols];
+                for _ in 0..reps_cols {
+                    data.extend_from_slice(row);
                 }
             }
         }
 
-        let out_shape = match (self.shape.len(), other.shape.len()) {
-            (1, 1) => vec![1],
-            (1, 2) => vec![b_cols],
-            (2, 1) => vec![a_rows],
-            _ => vec![a_rows, b_cols],
+        Tensor::new(data, vec![rows * reps_rows, out_cols])
+    }
+
+    /// Multiplies every element of row `i` of a 2D tensor by `scales[i]`,
+    /// where `scales` is a `[rows]` or `[rows, 1]` tensor
+    pub fn scale_rows(&self, scales: &Tensor) -> Result<Tensor, TensorError> {
+        let [rows, _] = self.shape[..] else {
+            return Err(TensorError::InvalidRank);
         };
+        match scales.shape[..] {
+            [n] | [n, 1] if n == rows => {}
+            _ => return Err(TensorError::ShapeMismatch),
+        }
 
-        Tensor::new(result_data, out_shape)
+        let data = self
+            .rows()
+            .zip(&scales.data)
+            .flat_map(|(row, &s)| row.iter().map(move |&v| v * s))
+            .collect();
+        Tensor::new(data, self.shape.clone())
     }
 
+    /// Subtracts a `[cols]` or `[1, cols]` vector from every row of a 2D tensor,
+    /// e.g. to center the columns on their `sum(Some(0))` mean
+    pub fn sub_row_vector(&self, row: &Tensor) -> Result<Tensor, TensorError> {
+        let [_, cols] = self.shape[..] else {
+            return Err(TensorError::InvalidRank);
+        };
+        match row.shape[..] {
+            [n] | [1, n] if n == cols => {}
+            _ => return Err(TensorError::ShapeMismatch),
+        }
+
+        let data = self
+            .rows()
+            .flat_map(|r| r.iter().zip(&row.data).map(|(v, m)| v - m))
+            .collect();
+        Tensor::new(data, self.shape.clone())
+    }
+
+    /// Diagonal of a square 2D tensor as an `[n]` tensor
+    pub fn diag(&self) -> Result<Tensor, TensorError> {
+        let [rows, cols] = self.shape[..] else {
+            return Err(TensorError::InvalidRank);
+        };
+        if rows != cols {
+            return Err(TensorError::ShapeMismatch);
+        }
+
+        let data = (0..rows).map(|i| self.data[i * cols + i]).collect();
+        Tensor::new(data, vec![rows])
+    }
+
+    /// Sum of the diagonal of a square 2D tensor, as a `[1]` tensor
+    pub fn trace(&self) -> Result<Tensor, TensorError> {
+        self.diag()?.sum(None)
+    }
+
+    /// Cache-friendly matrix product. A zero in `self` skips its whole row of `other`, so
+    /// a NaN or infinity there never reaches the result: `0 * NaN` counts as 0 here, where
+    /// IEEE (and `matmul_naive`) give NaN. Use `matmul_strict` when that propagation matters.
     pub fn matmul(&self, other: &Tensor) -> Result<Tensor, TensorError> {
+        let (a_cols, b_cols, out_shape) = self._matmul_shape(other)?;
+
+        let mut data = vec![0.0; out_shape.iter().product()];
+        Self::_matmul_accumulate(&self.data, &other.data, a_cols, b_cols, &mut data, true);
+
+        let result = Tensor::new(data, out_shape)?;
+
+        if self.matmul_cross_checks(other) {
+            result.assert_matmul_agrees(&self.matmul_naive(other)?);
+        }
+        Ok(result)
+    }
+
+    /// Whether `matmul(other)` also runs `matmul_naive` and compares the two: only in debug
+    /// builds, and only up to a fixed product size
+    pub fn matmul_cross_checks(&self, other: &Tensor) -> bool {
+        match self._matmul_shape(other) {
+            Ok((a_cols, _, out_shape)) => {
+                let work = out_shape.iter().product::<usize>() * a_cols;
+                MATMUL_CROSS_CHECK && work <= MATMUL_CROSS_CHECK_MAX_WORK
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Same loop order as `matmul`, without the zero skip, so NaN and infinity in either
+    /// operand propagate exactly like in `matmul_naive`
+    pub fn matmul_strict(&self, other: &Tensor) -> Result<Tensor, TensorError> {
+        let (a_cols, b_cols, out_shape) = self._matmul_shape(other)?;
+
+        let mut data = vec![0.0; out_shape.iter().product()];
+        Self::_matmul_accumulate(&self.data, &other.data, a_cols, b_cols, &mut data, false);
+
+        Tensor::new(data, out_shape)
+    }
+
+    /// Same IKJ product as `matmul`, zero skip included, but each output row is accumulated
+    /// in explicit `f32x8` lanes with a scalar loop for the columns left over. Needs the
+    /// `simd` feature, which in turn needs a nightly toolchain.
+    #[cfg(feature = "simd")]
+    pub fn matmul_simd(&self, other: &Tensor) -> Result<Tensor, TensorError> {
+        use std::simd::f32x8;
+
+        let (a_cols, b_cols, out_shape) = self._matmul_shape(other)?;
+
+        let mut data = vec![0.0; out_shape.iter().product()];
+        for (i, a_row) in self.data.chunks_exact(a_cols.max(1)).enumerate() {
+            let out_row = &mut data[i * b_cols..(i + 1) * b_cols];
+
+            for (k, &aik) in a_row.iter().enumerate() {
+                if aik == 0.0 {
+                    continue;
+                }
+
+                let b_row = &other.data[k * b_cols..(k + 1) * b_cols];
+                let scale = f32x8::splat(aik);
+
+                let mut out_chunks = out_row.chunks_exact_mut(8);
+                let mut b_chunks = b_row.chunks_exact(8);
+                for (out, b) in (&mut out_chunks).zip(&mut b_chunks) {
+                    let acc = f32x8::from_slice(out) + scale * f32x8::from_slice(b);
+                    acc.copy_to_slice(out);
+                }
+
+                let out_rest = out_chunks.into_remainder();
+                for (out_val, &b_val) in out_rest.iter_mut().zip(b_chunks.remainder()) {
+                    *out_val += aik * b_val;
+                }
+            }
+        }
+
+        Tensor::new(data, out_shape)
+    }
+
+    /// Runs both `matmul` and `matmul_naive` and panics if they disagree,
+    /// whatever the build profile or matrix size
+    pub fn matmul_checked(&self, other: &Tensor) -> Result<Tensor, TensorError> {
+        let result = self.matmul(other)?;
+        result.assert_matmul_agrees(&self.matmul_naive(other)?);
+        Ok(result)
+    }
+
+    /// Panics unless this `matmul` result matches the `naive` reference within a relative
+    /// tolerance. Where the reference is NaN or infinite (0 * inf, which the zero skip turns
+    /// into 0) any value is accepted, but a finite reference needs a finite, close value.
+    pub fn assert_matmul_agrees(&self, naive: &Tensor) {
+        assert_eq!(
+            self.shape, naive.shape,
+            "matmul and matmul_naive shapes differ"
+        );
+
+        for (i, (&fast, &slow)) in self.data.iter().zip(&naive.data).enumerate() {
+            // The zero skip legitimately differs on 0 * inf, where the reference is not finite
+            if !slow.is_finite() {
+                continue;
+            }
+            assert!(
+                fast.is_finite(),
+                "matmul gives {} at {} where matmul_naive gives {}",
+                fast,
+                i,
+                slow
+            );
+            let tolerance = 1e-4 * (1.0 + fast.abs().max(slow.abs()));
+            assert!(
+                (fast - slow).abs() <= tolerance,
+                "matmul and matmul_naive differ at {}: {} vs {}",
+                i,
+                fast,
+                slow
+            );
+        }
+    }
+
+    /// Same as `matmul`, but writes into an existing tensor of the product shape
+    /// instead of allocating a new one
+    pub fn matmul_into(&self, other: &Tensor, out: &mut Tensor) -> Result<(), TensorError> {
+        let (a_cols, b_cols, out_shape) = self._matmul_shape(other)?;
+
+        if out.shape != out_shape {
+            return Err(TensorError::ShapeMismatch);
+        }
+
+        out.data.fill(0.0);
+        Self::_matmul_accumulate(&self.data, &other.data, a_cols, b_cols, &mut out.data, true);
+
+        Ok(())
+    }
+
+    /// Multiplies matching slices of a `[b, m, k]` and a `[b, k, n]` tensor into `[b, m, n]`.
+    /// Each slice goes through the same kernel as `matmul`, zero skip included.
+    pub fn batched_matmul(&self, other: &Tensor) -> Result<Tensor, TensorError> {
+        let ([batch, m, k], [other_batch, other_k, n]) = (&self.shape[..], &other.shape[..]) else {
+            return Err(TensorError::InvalidRank);
+        };
+        let (batch, m, k, n) = (*batch, *m, *k, *n);
+
+        if batch != *other_batch || k != *other_k {
+            return Err(TensorError::ShapeMismatch);
+        }
+
+        let mut data = vec![0.0; batch * m * n];
+        for i in 0..batch {
+            Self::_matmul_accumulate(
+                &self.data[i * m * k..(i + 1) * m * k],
+                &other.data[i * k * n..(i + 1) * k * n],
+                k,
+                n,
+                &mut data[i * m * n..(i + 1) * m * n],
+                true,
+            );
+        }
+
+        Tensor::new(data, vec![batch, m, n])
+    }
+
+    /// Validates the operands and returns (a_cols, b_cols, output shape)
+    fn _matmul_shape(&self, other: &Tensor) -> Result<(usize, usize, Vec<usize>), TensorError> {
         let (a_rows, a_cols) = match self.shape.as_slice() {
             [c] => (1, *c),
             [r, c] => (*r, *c),
@@ -245,20 +945,39 @@ impl Tensor {
             return Err(TensorError::ShapeMismatch);
         }
 
-        let mut data = vec![0.0; a_rows * b_cols];
+        let out_shape = match (self.shape.len(), other.shape.len()) {
+            (1, 1) => vec![1],
+            (1, 2) => vec![b_cols],
+            (2, 1) => vec![a_rows],
+            _ => vec![a_rows, b_cols],
+        };
+
+        Ok((a_cols, b_cols, out_shape))
+    }
 
+    /// Adds a x b onto `data`, which must be zeroed by the caller.
+    /// `skip_zeros` leaves out the rows of `b` that a zero in `a` would multiply.
+    fn _matmul_accumulate(
+        a: &[f32],
+        b: &[f32],
+        a_cols: usize,
+        b_cols: usize,
+        data: &mut [f32],
+        skip_zeros: bool,
+    ) {
         // The core optimization: IKJ order with Iterators
-        for (i, a_row) in self.data.chunks_exact(a_cols).enumerate() {
+        // With a_cols == 0 there is nothing to accumulate and `data` stays zero
+        for (i, a_row) in a.chunks_exact(a_cols.max(1)).enumerate() {
             let out_row_start = i * b_cols;
             let out_row = &mut data[out_row_start..out_row_start + b_cols];
 
             for (k, &aik) in a_row.iter().enumerate() {
-                if aik == 0.0 {
+                if skip_zeros && aik == 0.0 {
                     continue;
                 } // Skip zeros for a small speed boost
 
                 let b_row_start = k * b_cols;
-                let b_row = &other.data[b_row_start..b_row_start + b_cols];
+                let b_row = &b[b_row_start..b_row_start + b_cols];
 
                 // This zip() is the key to SIMD and removing bounds checks
                 for (out_val, &b_val) in out_row.iter_mut().zip(b_row.iter()) {
@@ -266,21 +985,13 @@ impl Tensor {
                 }
             }
         }
-
-        let out_shape = match (self.shape.len(), other.shape.len()) {
-            (1, 1) => vec![1],
-            (1, 2) => vec![b_cols],
-            (2, 1) => vec![a_rows],
-            _ => vec![a_rows, b_cols],
-        };
-
-        Ok(Tensor {
-            data,
-            shape: out_shape,
-        })
     }
 
     pub fn sum(&self, axis: Option<usize>) -> Result<Tensor, TensorError> {
+        if self.shape.len() > 2 && axis.is_some() {
+            return Err(TensorError::InvalidRank);
+        }
+
         match axis {
             None => {
                 let sum: f32 = self.data.iter().sum();
@@ -295,9 +1006,10 @@ impl Tensor {
                 let cols = self.shape[1];
                 let mut result_data = vec![0.0; cols];
 
-                for r in 0..rows {
-                    for c in 0..cols {
-                        result_data[c] += self.data[r * cols + c];
+                // max(1) keeps chunks_exact valid for [rows, 0], which has no data anyway
+                for row in self.data.chunks_exact(cols.max(1)).take(rows) {
+                    for (acc, &val) in result_data.iter_mut().zip(row) {
+                        *acc += val;
                     }
                 }
                 Tensor::new(result_data, vec![cols])
@@ -311,15 +1023,293 @@ impl Tensor {
                 let cols = self.shape[1];
                 let mut result_data = vec![0.0; rows];
 
-                for r in 0..rows {
-                    for c in 0..cols {
-                        result_data[r] += self.data[r * cols + c];
+                // A [rows, 0] tensor has no chunks, leaving every row sum at 0
+                for (acc, row) in result_data
+                    .iter_mut()
+                    .zip(self.data.chunks_exact(cols.max(1)))
+                {
+                    *acc = row.iter().sum();
+                }
+                Tensor::new(result_data, vec![rows])
+            }
+
+            _ => Err(TensorError::InvalidRank),
+        }
+    }
+
+    /// Same as `sum`, but multiplying. A reduction over zero elements gives 1.0,
+    /// the empty product.
+    pub fn product(&self, axis: Option<usize>) -> Result<Tensor, TensorError> {
+        if self.shape.len() > 2 && axis.is_some() {
+            return Err(TensorError::InvalidRank);
+        }
+
+        match (axis, &self.shape[..]) {
+            (None, _) | (Some(0 | 1), [_]) => {
+                Tensor::new(vec![self.data.iter().product()], vec![1])
+            }
+
+            (Some(0), &[rows, cols]) => {
+                let mut result_data = vec![1.0; cols];
+                for row in self.data.chunks_exact(cols.max(1)).take(rows) {
+                    for (acc, &val) in result_data.iter_mut().zip(row) {
+                        *acc *= val;
                     }
                 }
+                Tensor::new(result_data, vec![cols])
+            }
+
+            (Some(1), &[rows, cols]) => {
+                // A [rows, 0] tensor has no chunks, leaving every row product at 1
+                let mut result_data = vec![1.0; rows];
+                for (acc, row) in result_data
+                    .iter_mut()
+                    .zip(self.data.chunks_exact(cols.max(1)))
+                {
+                    *acc = row.iter().product();
+                }
                 Tensor::new(result_data, vec![rows])
             }
 
             _ => Err(TensorError::InvalidRank),
         }
     }
+
+    /// Smallest element, with the same axis convention as `sum`: `None` gives a `[1]` tensor,
+    /// axis 0 the minimum of every column and axis 1 the minimum of every row.
+    /// Reducing over zero elements is a `ShapeMismatch`, as there is no minimum.
+    pub fn min(&self, axis: Option<usize>) -> Result<Tensor, TensorError> {
+        Ok(self._min_lanes(axis)?.1)
+    }
+
+    /// Position of the smallest element along the reduced axis (the first one on ties).
+    /// For `None` the single index is into the row-major data.
+    pub fn argmin(&self, axis: Option<usize>) -> Result<Vec<usize>, TensorError> {
+        Ok(self._min_lanes(axis)?.0)
+    }
+
+    /// Index within the lane and value of the minimum of every lane `axis` reduces over
+    fn _min_lanes(&self, axis: Option<usize>) -> Result<(Vec<usize>, Tensor), TensorError> {
+        let (lane_count, lane_len, step, lane_stride) = self._lane_layout(axis)?;
+        if lane_len == 0 {
+            return Err(TensorError::ShapeMismatch);
+        }
+
+        let (indices, values) = (0..lane_count)
+            .map(|lane| {
+                let lane_data = (0..lane_len).map(|k| self.data[lane * lane_stride + k * step]);
+                lane_data
+                    .enumerate()
+                    .fold((0, f32::INFINITY), |best, (k, v)| {
+                        if k == 0 || v < best.1 { (k, v) } else { best }
+                    })
+            })
+            .unzip();
+
+        Ok((indices, Tensor::new(values, self._reduced_shape(axis))?))
+    }
+
+    /// ln(sum(exp(x))) with the same axis convention as `sum`, computed as
+    /// max + ln(sum(exp(x - max))) so large values cannot overflow
+    pub fn logsumexp(&self, axis: Option<usize>) -> Result<Tensor, TensorError> {
+        let (lane_count, lane_len, step, lane_stride) = self._lane_layout(axis)?;
+
+        let values = (0..lane_count)
+            .map(|lane| {
+                let lane_data = || (0..lane_len).map(|k| self.data[lane * lane_stride + k * step]);
+                let max = lane_data().fold(f32::NEG_INFINITY, f32::max);
+                // An empty lane gives ln(0) = -inf, and infinities would turn x - max into NaN
+                if !max.is_finite() {
+                    return max;
+                }
+                max + lane_data().map(|v| (v - max).exp()).sum::<f32>().ln()
+            })
+            .collect();
+
+        Tensor::new(values, self._reduced_shape(axis))
+    }
+
+    /// Turns every row of a 2D tensor of logits into probabilities that sum to 1.
+    /// The row maximum is subtracted before exponentiating, so large logits cannot overflow.
+    pub fn softmax_rows(&self) -> Result<Tensor, TensorError> {
+        if self.shape.len() != 2 {
+            return Err(TensorError::InvalidRank);
+        }
+
+        let mut data = Vec::with_capacity(self.data.len());
+        for row in self.rows() {
+            let max = row.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            let start = data.len();
+            data.extend(row.iter().map(|&x| (x - max).exp()));
+
+            let total: f32 = data[start..].iter().sum();
+            for v in &mut data[start..] {
+                *v /= total;
+            }
+        }
+
+        Tensor::new(data, self.shape.clone())
+    }
+
+    /// Reductions see the data as lanes: lane `l` holds `lane_len` elements `step` apart,
+    /// starting at `l * lane_stride`. Returns (lane_count, lane_len, step, lane_stride).
+    fn _lane_layout(
+        &self,
+        axis: Option<usize>,
+    ) -> Result<(usize, usize, usize, usize), TensorError> {
+        if self.shape.len() > 2 && axis.is_some() {
+            return Err(TensorError::InvalidRank);
+        }
+
+        match (axis, &self.shape[..]) {
+            (None, _) | (Some(0 | 1), [_]) => Ok((1, self.data.len(), 1, 0)),
+            (Some(0), &[rows, cols]) => Ok((cols, rows, cols, 1)),
+            (Some(1), &[rows, cols]) => Ok((rows, cols, 1, cols)),
+            _ => Err(TensorError::InvalidRank),
+        }
+    }
+
+    /// Shape of a lane reduction: one value per lane for an axis of a 2D tensor, else `[1]`
+    fn _reduced_shape(&self, axis: Option<usize>) -> Vec<usize> {
+        match (axis, &self.shape[..]) {
+            (Some(0), &[_, cols]) => vec![cols],
+            (Some(1), &[rows, _]) => vec![rows],
+            _ => vec![1],
+        }
+    }
+
+    /// Same as `sum(Some(axis))`, but the reduced dimension stays as a 1, so a `[rows, cols]`
+    /// tensor gives `[1, cols]` for axis 0 and `[rows, 1]` for axis 1
+    pub fn sum_keepdims(&self, axis: usize) -> Result<Tensor, TensorError> {
+        let summed = self.sum(Some(axis))?;
+        if self.shape.len() < 2 {
+            return Ok(summed);
+        }
+
+        let mut shape = self.shape.clone();
+        shape[axis] = 1;
+        Tensor::new(summed.data, shape)
+    }
+
+    /// Running sums with the same shape as the input: down the columns for axis 0,
+    /// along the rows for axis 1. A 1D tensor only has axis 0.
+    pub fn cumsum(&self, axis: usize) -> Result<Tensor, TensorError> {
+        let mut data = self.data.clone();
+
+        match (self.shape.as_slice(), axis) {
+            ([_], 0) => {
+                for i in 1..data.len() {
+                    data[i] += data[i - 1];
+                }
+            }
+            ([_, cols], 0) => {
+                for i in *cols..data.len() {
+                    data[i] += data[i - cols];
+                }
+            }
+            ([_, cols], 1) => {
+                for row in data.chunks_exact_mut((*cols).max(1)) {
+                    for c in 1..row.len() {
+                        row[c] += row[c - 1];
+                    }
+                }
+            }
+            _ => return Err(TensorError::InvalidRank),
+        }
+
+        Tensor::new(data, self.shape.clone())
+    }
+
+    /// Same as `sum`, but with Kahan (compensated) summation, which keeps the rounding
+    /// error of long reductions from piling up
+    pub fn sum_kahan(&self, axis: Option<usize>) -> Result<Tensor, TensorError> {
+        if self.shape.len() > 2 && axis.is_some() {
+            return Err(TensorError::InvalidRank);
+        }
+
+        match axis {
+            None => Tensor::new(vec![Self::_kahan_sum(self.data.iter().copied())], vec![1]),
+
+            Some(0) => {
+                if self.shape.len() < 2 {
+                    return self.sum_kahan(None);
+                }
+                let cols = self.shape[1];
+                let result_data = (0..cols)
+                    .map(|c| Self::_kahan_sum(self.data.iter().skip(c).step_by(cols).copied()))
+                    .collect();
+                Tensor::new(result_data, vec![cols])
+            }
+
+            Some(1) => {
+                if self.shape.len() < 2 {
+                    return self.sum_kahan(None);
+                }
+                let rows = self.shape[0];
+                let cols = self.shape[1];
+                let result_data = (0..rows)
+                    .map(|r| Self::_kahan_sum(self.data[r * cols..(r + 1) * cols].iter().copied()))
+                    .collect();
+                Tensor::new(result_data, vec![rows])
+            }
+
+            _ => Err(TensorError::InvalidRank),
+        }
+    }
+
+    fn _kahan_sum(values: impl Iterator<Item = f32>) -> f32 {
+        let mut sum = 0.0f32;
+        // Low-order bits lost by the previous addition, fed back into the next one
+        let mut compensation = 0.0f32;
+        for v in values {
+            let y = v - compensation;
+            let t = sum + y;
+            compensation = (t - sum) - y;
+            sum = t;
+        }
+        sum
+    }
+
+    /// Counts the elements falling into each of `bins` equal-width bins spanning
+    /// `range` (min, max). Values outside the range go to the first or last bin,
+    /// NaNs are not counted.
+    pub fn histogram(&self, bins: usize, range: (f32, f32)) -> Vec<usize> {
+        let mut counts = vec![0; bins];
+        if bins == 0 {
+            return counts;
+        }
+
+        let (min, max) = range;
+        let width = (max - min) / bins as f32;
+        for &v in self.data.iter().filter(|v| !v.is_nan()) {
+            let bin = if v >= max {
+                bins - 1
+            } else if v <= min {
+                0
+            } else {
+                (((v - min) / width) as usize).min(bins - 1)
+            };
+            counts[bin] += 1;
+        }
+        counts
+    }
+
+    /// The value below which `q` percent (0 to 100, clamped) of the elements fall,
+    /// interpolating linearly between the two nearest sorted elements.
+    /// NaN for an empty tensor.
+    pub fn percentile(&self, q: f32) -> f32 {
+        if self.data.is_empty() {
+            return f32::NAN;
+        }
+
+        let mut sorted = self.data.clone();
+        sorted.sort_by(f32::total_cmp);
+
+        let rank = q.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f32;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let frac = rank - lower as f32;
+
+        sorted[lower] + (sorted[upper] - sorted[lower]) * frac
+    }
 }
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use build_your_own_nn::{
+        Layer,
+        activation::{Activation, ActivationType},
+        tensor::{Tensor, TensorError},
+    };
+
+    #[test]
+    fn test_softplus_large_input_is_stable() -> Result<(), TensorError> {
+        let mut softplus = Activation::new(ActivationType::Softplus);
+        let input = Tensor::new(vec![100.0, 500.0], vec![1, 2])?;
+
+        let output = softplus.forward(&input)?;
+        for (out, x) in output.data().iter().zip(input.data()) {
+            assert!(out.is_finite());
+            assert!((out - x).abs() < 1e-4);
+        }
+
+        let grad = softplus.backward(&Tensor::one(vec![1, 2])?, 0.0)?;
+        for g in grad.data() {
+            assert!((g - 1.0).abs() < 1e-6);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_softplus_matches_definition() -> Result<(), TensorError> {
+        let mut softplus = Activation::new(ActivationType::Softplus);
+        let input = Tensor::new(vec![-2.0, 0.0, 1.5], vec![3])?;
+
+        let output = softplus.forward(&input)?;
+        for (out, x) in output.data().iter().zip(input.data()) {
+            assert!((out - (1.0 + x.exp()).ln()).abs() < 1e-6);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_mish_reference_points() -> Result<(), TensorError> {
+        let mish = Activation::new(ActivationType::Mish);
+        let input = Tensor::new(vec![0.0, 1.0, -1.0, 50.0], vec![4])?;
+
+        let output = mish.forward_infer(&input)?;
+        let expected = [0.0, 0.865_098_4, -0.303_401_4, 50.0];
+        for (out, e) in output.data().iter().zip(expected) {
+            assert!((out - e).abs() < 1e-5);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_mish_gradient_matches_finite_difference() -> Result<(), TensorError> {
+        let mut mish = Activation::new(ActivationType::Mish);
+        let xs = vec![-3.0, -0.5, 0.0, 0.7, 2.5];
+        let input = Tensor::new(xs.clone(), vec![1, 5])?;
+
+        mish.forward(&input)?;
+        let grad = mish.backward(&Tensor::one(vec![1, 5])?, 0.0)?;
+
+        let h = 1e-2;
+        let plus = mish.forward_infer(&input.add_scalar(h)?)?;
+        let minus = mish.forward_infer(&input.sub_scalar(h)?)?;
+        for i in 0..xs.len() {
+            let numeric = (plus.data()[i] - minus.data()[i]) / (2.0 * h);
+            assert!((grad.data()[i] - numeric).abs() < 1e-3);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_relu6_saturates_with_zero_gradient() -> Result<(), TensorError> {
+        let mut relu6 = Activation::new(ActivationType::ReLU6);
+        let input = Tensor::new(vec![-3.0, 0.0, 2.5, 6.0, 7.0, 100.0], vec![1, 6])?;
+
+        let output = relu6.forward(&input)?;
+        assert_eq!(output.data(), &[0.0, 0.0, 2.5, 6.0, 6.0, 6.0]);
+
+        let grad = relu6.backward(&Tensor::one(vec![1, 6])?, 0.0)?;
+        assert_eq!(grad.data(), &[0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tanh_prime_uses_forward_output() -> Result<(), TensorError> {
+        let mut tanh = Activation::new(ActivationType::Tanh);
+        let input = Tensor::new(vec![-2.0, -0.3, 0.0, 0.8, 3.0], vec![1, 5])?;
+
+        let output = tanh.forward(&input)?;
+        let prime = tanh.backward(&Tensor::one(vec![1, 5])?, 0.0)?;
+
+        for (p, out) in prime.data().iter().zip(output.data()) {
+            assert_eq!(*p, 1.0 - out * out);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_tanh_saturates_on_large_inputs() -> Result<(), TensorError> {
+        let tanh = Activation::new(ActivationType::Tanh);
+        let input = Tensor::new(vec![-100.0, 0.5, 100.0], vec![1, 3])?;
+
+        // exp(100) overflows f32, so the exp ratio would give inf / inf = NaN here
+        let output = tanh.forward_infer(&input)?;
+        assert_eq!(output.data(), &[-1.0, 0.5f32.tanh(), 1.0]);
+        Ok(())
+    }
+}
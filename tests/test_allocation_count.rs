@@ -0,0 +1,39 @@
+// The counter is global, so these tests live in their own binary where no other
+// test allocates tensors in parallel.
+#[cfg(test)]
+mod tests {
+    use build_your_own_nn::{
+        DefaultRng,
+        linear::Linear,
+        loss::mse_loss_gradient,
+        neural_network::NetworkBuilder,
+        tensor::{Tensor, TensorError, allocation_count, reset_allocation_count},
+    };
+
+    #[test]
+    fn test_allocations_of_one_fit_epoch() -> Result<(), TensorError> {
+        let mut nn = NetworkBuilder::new()
+            .add_layer(Box::new(Linear::new(2, 1, &mut DefaultRng::new(3))))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+        let x = Tensor::new(vec![0.0, 1.0, 1.0, 1.0], vec![2, 2])?;
+        let y = Tensor::new(vec![1.0, 3.0], vec![2, 1])?;
+
+        reset_allocation_count();
+        nn.fit(&x, &y, 1, 0.1)?;
+        let one_epoch = allocation_count();
+
+        reset_allocation_count();
+        nn.fit(&x, &y, 2, 0.1)?;
+        let two_epochs = allocation_count();
+
+        if cfg!(feature = "alloc-counter") {
+            assert!(one_epoch > 0);
+            assert_eq!(two_epochs, 2 * one_epoch);
+        } else {
+            assert_eq!((one_epoch, two_epochs), (0, 0));
+        }
+        Ok(())
+    }
+}
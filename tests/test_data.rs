@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use build_your_own_nn::{
+        DefaultRng,
+        data::{Scaler, generate_blobs, shuffle_rows},
+        tensor::{Tensor, TensorError},
+    };
+
+    fn dataset() -> (Tensor, Tensor) {
+        // Each x row is [i, 10 * i] and its target is i, so pairing is easy to check
+        let x: Vec<f32> = (0..8).flat_map(|i| [i as f32, 10.0 * i as f32]).collect();
+        let y: Vec<f32> = (0..8).map(|i| i as f32).collect();
+        (
+            Tensor::new(x, vec![8, 2]).unwrap(),
+            Tensor::new(y, vec![8, 1]).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_shuffle_rows_is_reproducible() -> Result<(), TensorError> {
+        let (x, y) = dataset();
+
+        let first = shuffle_rows(&x, &y, &mut DefaultRng::new(11))?;
+        let second = shuffle_rows(&x, &y, &mut DefaultRng::new(11))?;
+
+        assert_eq!(first, second);
+        assert_ne!(first.0, x);
+        Ok(())
+    }
+
+    #[test]
+    fn test_shuffle_rows_preserves_pairs() -> Result<(), TensorError> {
+        let (x, y) = dataset();
+        let (sx, sy) = shuffle_rows(&x, &y, &mut DefaultRng::new(3))?;
+
+        for (row, &target) in sx.data().chunks(2).zip(sy.data()) {
+            assert_eq!(row, &[target, 10.0 * target]);
+        }
+
+        let mut targets = sy.data().to_vec();
+        targets.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(targets, y.data());
+        Ok(())
+    }
+
+    #[test]
+    fn test_shuffle_rows_row_mismatch() {
+        let (x, _) = dataset();
+        let y = Tensor::new(vec![0.0; 3], vec![3, 1]).unwrap();
+
+        let result = shuffle_rows(&x, &y, &mut DefaultRng::new(1));
+        assert_eq!(result.err(), Some(TensorError::ShapeMismatch));
+    }
+
+    #[test]
+    fn test_generate_blobs_one_hot_clusters() {
+        let centers = [(-5.0, 0.0), (5.0, 0.0), (0.0, 8.0)];
+        let (x, y) = generate_blobs(300, &centers, 0.5, &mut DefaultRng::new(21));
+
+        assert_eq!(x.shape(), &[300, 2]);
+        assert_eq!(y.shape(), &[300, 3]);
+
+        let mut sums = [(0.0, 0.0); 3];
+        for (point, label) in x.rows().zip(y.rows()) {
+            assert_eq!(label.iter().sum::<f32>(), 1.0);
+            let class = label.iter().position(|&v| v == 1.0).unwrap();
+
+            let (cx, cy) = centers[class];
+            let dist = ((point[0] - cx).powi(2) + (point[1] - cy).powi(2)).sqrt();
+            assert!(
+                dist < 3.0,
+                "point {:?} is {} away from its center",
+                point,
+                dist
+            );
+
+            sums[class].0 += point[0];
+            sums[class].1 += point[1];
+        }
+
+        // 100 samples per class, so each mean lands close to its center
+        for ((sx, sy), (cx, cy)) in sums.iter().zip(centers) {
+            assert!((sx / 100.0 - cx).abs() < 0.2 && (sy / 100.0 - cy).abs() < 0.2);
+        }
+    }
+
+    #[test]
+    fn test_scaler_round_trip() -> Result<(), TensorError> {
+        let (x, _) = dataset();
+        let mut scaler = Scaler::new();
+        scaler.fit(&x)?;
+
+        let scaled = scaler.transform(&x)?;
+        for col in 0..2 {
+            let mean: f32 = scaled.data().iter().skip(col).step_by(2).sum::<f32>() / 8.0;
+            assert!(mean.abs() < 1e-5);
+        }
+
+        let restored = scaler.inverse_transform(&scaled)?;
+        for (a, b) in restored.data().iter().zip(x.data()) {
+            assert!((a - b).abs() < 1e-4);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_scaler_uses_training_statistics() -> Result<(), TensorError> {
+        let (x, _) = dataset();
+        let mut scaler = Scaler::new();
+        scaler.fit(&x)?;
+
+        // A single test row must not be centred on itself
+        let test = Tensor::new(vec![3.5, 35.0], vec![1, 2])?;
+        let scaled = scaler.transform(&test)?;
+        assert!(scaled.data()[0].abs() < 1e-5);
+
+        let wide = Tensor::new(vec![0.0; 3], vec![1, 3])?;
+        assert_eq!(
+            scaler.transform(&wide).err(),
+            Some(TensorError::ShapeMismatch)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,295 @@
+#[cfg(test)]
+mod tests {
+    use build_your_own_nn::{
+        DefaultRng,
+        activation::{Activation, ActivationType},
+        examples::{
+            AnimationConfig,
+            benchmark::{run_benchmark_csv, run_network_benchmark},
+            confidence_band, confidence_grid, decision_frame, decision_grid,
+            image_reconstructor::{self, ReconstructorConfig},
+            linear_regression::linear_regression,
+            neural_network_logic, neural_network_logic_animated, neural_network_xor_animated,
+            save_decision_boundary, two_moons,
+        },
+        image_utils::{Palette, PlotColor, read_pbm},
+        linear::Linear,
+        loss::mse_loss_gradient,
+        neural_network::NetworkBuilder,
+        tensor::{Tensor, TensorError},
+    };
+
+    #[test]
+    fn test_decision_grid_point_count() -> Result<(), TensorError> {
+        let (nx, ny) = (7, 4);
+        let traces = decision_grid(|x, _| Ok(x), (0.0, 1.0), (0.0, 1.0), nx, ny)?;
+
+        let total: usize = traces.iter().map(|t| t.x.len()).sum();
+        assert_eq!(total, (nx + 1) * (ny + 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decision_grid_splits_regions() -> Result<(), TensorError> {
+        // Probability is the x coordinate, so everything right of 0.5 is class 1
+        let traces = decision_grid(|x, _| Ok(x), (0.0, 1.0), (0.0, 1.0), 4, 2)?;
+
+        assert!(traces[0].x.iter().all(|&x| x > 0.5));
+        assert!(traces[1].x.iter().all(|&x| x <= 0.5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_confidence_band() -> Result<(), TensorError> {
+        let bands = [
+            (1.0, PlotColor::BoldCyan),
+            (0.95, PlotColor::BoldCyan),
+            (0.75, PlotColor::Cyan),
+            (0.55, PlotColor::DimCyan),
+            (0.5, PlotColor::DimMagenta),
+            (0.45, PlotColor::DimMagenta),
+            (0.2, PlotColor::Magenta),
+            (0.05, PlotColor::BoldMagenta),
+            (0.0, PlotColor::BoldMagenta),
+        ];
+        for (p, expected) in bands {
+            assert_eq!(confidence_band(p), expected, "p = {}", p);
+        }
+
+        // Every grid point lands in exactly one band trace
+        let traces = confidence_grid(|x, _| Ok(x), (0.0, 1.0), (0.0, 1.0), 20, 2)?;
+        assert_eq!(traces.len(), 6);
+        let total: usize = traces.iter().map(|t| t.x.len()).sum();
+        assert_eq!(total, 21 * 3);
+
+        // Legend entries read as classes, not color names
+        let names: Vec<&str> = traces.iter().map(|t| t.name.as_str()).collect();
+        assert!(names.contains(&"class 1 (confident)"));
+        assert!(names.contains(&"class 0 (uncertain)"));
+        assert!(names.iter().all(|n| n.starts_with("class ")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_gate_regions_follow_sigmoid() -> Result<(), TensorError> {
+        let mut linear = Linear::new_uniform(3, 1, &mut DefaultRng::new(1));
+        // Boundary x + y = 21 cuts the plotted grid diagonally
+        let weights = Tensor::new(vec![1.0, 1.0, -21.0], vec![3, 1])?;
+        linear.set_weight(weights.clone());
+
+        let traces = neural_network_logic_animated::gate_regions(&linear)?;
+
+        let mut points = Vec::new();
+        for gx in (0..=20).step_by(2) {
+            for gy in (0..=20).step_by(2) {
+                points.extend([gx as f32, gy as f32, 1.0]);
+            }
+        }
+        let grid = Tensor::new(points, vec![121, 3])?;
+        let probs = grid.matmul(&weights)?.sigmoid()?;
+
+        let mut expected_on = Vec::new();
+        let mut expected_off = Vec::new();
+        for (point, &p) in grid.rows().zip(probs.data()) {
+            if p > 0.5 {
+                expected_on.push((point[0], point[1]));
+            } else {
+                expected_off.push((point[0], point[1]));
+            }
+        }
+
+        let actual = |t: usize| -> Vec<(f32, f32)> {
+            traces[t]
+                .x
+                .iter()
+                .copied()
+                .zip(traces[t].y.iter().copied())
+                .collect()
+        };
+        assert_eq!(actual(0), expected_on);
+        assert_eq!(actual(1), expected_off);
+        assert!(!expected_on.is_empty() && !expected_off.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_linear_regression_converges() -> Result<(), TensorError> {
+        let (prediction, loss) = linear_regression(&mut DefaultRng::new(73))?;
+
+        assert_eq!(prediction.shape(), &[5, 1]);
+        // The least-squares optimum for this data is an MSE of about 0.4464
+        assert!(loss < 0.45, "final MSE {} is too high", loss);
+        Ok(())
+    }
+
+    #[test]
+    fn test_animation_config_defaults() {
+        let config = AnimationConfig::default();
+
+        assert_eq!(config.epochs, 25_000);
+        assert_eq!(config.render_every, 100);
+        assert_eq!(config.frame_delay_ms, 10);
+        assert_eq!((config.width, config.height), (70, 25));
+        assert_eq!(config.palette, Palette::Standard);
+        assert_eq!(neural_network_xor_animated::animation_config(), config);
+
+        let moons = two_moons::animation_config();
+        assert_eq!((moons.epochs, moons.render_every), (100_000, 500));
+        assert_eq!(moons.frame_delay_ms, 50);
+        assert_eq!((moons.width, moons.height), (100, 30));
+        assert_eq!((moons.frame_dir, moons.palette), (None, Palette::Standard));
+
+        assert!(config.should_render(0));
+        assert!(config.should_render(200));
+        assert!(!config.should_render(150));
+    }
+
+    #[test]
+    fn test_save_decision_boundary_dimensions() {
+        // sigmoid(10 * x) is class 1 exactly on the right half of [-1, 1]
+        let mut linear = Linear::new_uniform(3, 1, &mut DefaultRng::new(1));
+        linear.set_weight(Tensor::new(vec![10.0, 0.0, 0.0], vec![3, 1]).unwrap());
+        let nn = NetworkBuilder::new()
+            .add_layer(Box::new(linear))
+            .add_layer(Box::new(Activation::new(ActivationType::Sigmoid)))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        let path = std::env::temp_dir().join("byonn_test_decision_boundary.pbm");
+        let path = path.to_str().unwrap();
+        save_decision_boundary(&nn, (-1.0, 1.0, -1.0, 1.0), (6, 3), path).unwrap();
+
+        let (w, h, pixels) = read_pbm(path).unwrap();
+        assert_eq!((w, h), (6, 3));
+        assert_eq!(pixels.len(), 18);
+        for row in pixels.chunks(6) {
+            assert_eq!(row, &[0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn test_decision_frame_pixel_order() {
+        // Row-major from the top-left pixel centre, so each value encodes its position
+        let frame = decision_frame(|x, y| Ok(x + 10.0 * y), (0.0, 2.0, 0.0, 2.0), (2, 2)).unwrap();
+
+        assert_eq!(frame, vec![15.5, 16.5, 5.5, 6.5]);
+    }
+
+    #[test]
+    fn test_benchmark_csv_rows() {
+        let sizes = [2, 3, 16];
+        let csv = run_benchmark_csv(&sizes, ',');
+        let mut lines = csv.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some(
+                "size,naive_ns,optimized_ns,speedup,\
+                 transpose_naive_ns,transpose_blocked_ns,transpose_speedup"
+            )
+        );
+        let rows: Vec<&str> = lines.collect();
+        assert_eq!(rows.len(), sizes.len());
+
+        for (row, size) in rows.iter().zip(sizes) {
+            let fields: Vec<f64> = row.split(',').map(|f| f.parse().unwrap()).collect();
+            assert_eq!(fields.len(), 7);
+            assert_eq!(fields[0], size as f64);
+        }
+
+        let tabbed = run_benchmark_csv(&[2], '\t');
+        assert_eq!(
+            tabbed.lines().next(),
+            Some(
+                "size\tnaive_ns\toptimized_ns\tspeedup\t\
+                 transpose_naive_ns\ttranspose_blocked_ns\ttranspose_speedup"
+            )
+        );
+    }
+
+    #[test]
+    fn test_network_benchmark_smoke() {
+        let stats = run_network_benchmark(&mut DefaultRng::new(5), 64, 3).unwrap();
+
+        assert!(stats.avg_epoch.as_nanos() > 0);
+        assert!(stats.samples_per_sec.is_finite() && stats.samples_per_sec > 0.0);
+    }
+
+    #[test]
+    fn test_reconstruction_threshold_changes_set_pixels() {
+        let mut nn = image_reconstructor::build_network(
+            &ReconstructorConfig::default(),
+            &mut DefaultRng::new(9),
+        )
+        .unwrap();
+        let set_pixels = |path: &str| {
+            read_pbm(path)
+                .unwrap()
+                .2
+                .iter()
+                .filter(|&&p| p == 1.0)
+                .count()
+        };
+
+        let low = std::env::temp_dir().join("byonn_test_reconstruct_low.pbm");
+        let low = low.to_str().unwrap();
+        let high = std::env::temp_dir().join("byonn_test_reconstruct_high.pbm");
+        let high = high.to_str().unwrap();
+
+        // Sigmoid output lies within [0, 1], so these are all-set and all-clear
+        image_reconstructor::draw_save_network_image(6, (6, 6), &mut nn, low, 0.0).unwrap();
+        image_reconstructor::draw_save_network_image(6, (6, 6), &mut nn, high, 1.5).unwrap();
+
+        assert_eq!(set_pixels(low), 36);
+        assert_eq!(set_pixels(high), 0);
+    }
+
+    #[test]
+    fn test_destination_coords_stay_in_training_range() {
+        // A 25x20 training image upscaled to 150
+        let coords = image_reconstructor::destination_coords(150, (25, 20));
+        assert_eq!(coords.len(), 150 * 150 * 2);
+        assert!(coords.iter().all(|v| (0.0..=1.0).contains(v)));
+
+        // The corners land on the first and last training pixels, normalized as in training
+        let rows: Vec<f32> = coords.iter().step_by(2).copied().collect();
+        let cols: Vec<f32> = coords.iter().skip(1).step_by(2).copied().collect();
+        assert_eq!(rows[0], 0.0);
+        assert_eq!(cols[0], 0.0);
+        assert!((rows.iter().cloned().fold(0.0, f32::max) - 19.0 / 20.0).abs() < 1e-6);
+        assert!((cols.iter().cloned().fold(0.0, f32::max) - 24.0 / 25.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reconstructor_config_depth() {
+        let shallow = ReconstructorConfig {
+            hidden: vec![8, 4],
+            ..ReconstructorConfig::default()
+        };
+        let nn = image_reconstructor::build_network(&shallow, &mut DefaultRng::new(2)).unwrap();
+
+        // Two hidden Linear + activation pairs, then the output Linear + Sigmoid
+        assert_eq!(nn.num_layers(), 6);
+        assert_eq!(nn.output_shape(&[10, 2]), Ok(vec![10, 1]));
+
+        let default = image_reconstructor::build_network(
+            &ReconstructorConfig::default(),
+            &mut DefaultRng::new(2),
+        )
+        .unwrap();
+        assert_eq!(default.num_layers(), 12);
+        // 2 -> 64 -> 64 -> 128 -> 64 -> 32 -> 1, weights only (Linear::new has no bias)
+        assert_eq!(default.num_parameters(), 22_688);
+    }
+
+    #[test]
+    fn test_multi_gate_learns_both_outputs() -> Result<(), TensorError> {
+        let (prediction, accuracy) =
+            neural_network_logic::demonstrate_multi_gate(&mut DefaultRng::new(12))?;
+
+        assert_eq!(prediction.shape(), &[4, 2]);
+        assert_eq!(accuracy, vec![1.0, 1.0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,338 @@
+#[cfg(test)]
+mod tests {
+    // Only image_utils is imported here on purpose: every example plots through this module,
+    // so the Trace and render_plot signatures must stay usable on their own.
+    use build_your_own_nn::image_utils::{
+        ImageError, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH, Palette, PlotColor, RenderStyle,
+        Trace, encode_gif, format_signed, read_pbm, read_pbm_for_nn, read_pbm_for_nn_streaming,
+        render_dual_plots, render_image_lines, render_plot, save_as_pbm, save_as_pbm_binary,
+        save_as_pbm_with_threshold, save_frame_pbm, terminal_size,
+    };
+
+    type Bounds = Option<(f32, f32, f32, f32)>;
+
+    #[test]
+    fn test_trace_from_image_utils_only() {
+        let trace = Trace {
+            name: "Points".into(),
+            x: vec![0.0, 1.0],
+            y: vec![0.0, 1.0],
+            color: PlotColor::Cyan,
+            is_line: false,
+            hide_axes: false,
+        };
+
+        // Titled signature shared by every animated example
+        let _: fn(&[Trace], usize, usize, Bounds, String) = render_plot;
+
+        assert_eq!(trace.x.len(), trace.y.len());
+        assert!(!trace.hide_axes);
+    }
+
+    #[test]
+    fn test_save_as_pbm_header_and_pixels() {
+        let path = std::env::temp_dir().join("byonn_test_save_as_pbm.pbm");
+        let path = path.to_str().unwrap();
+
+        save_as_pbm(path, 2, 2, &[1.0, 0.0, 0.2, 0.9]).unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+
+        assert_eq!(content, "P1\n2 2\n1 0\n0 1\n");
+    }
+
+    #[test]
+    fn test_save_as_pbm_gradient_tie_breaking() {
+        let path = std::env::temp_dir().join("byonn_test_pbm_gradient.pbm");
+        let path = path.to_str().unwrap();
+        let gradient = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+        save_as_pbm_with_threshold(path, 5, 1, &gradient, 0.5, true).unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(content, "P1\n5 1\n0 0 1 1 1\n");
+
+        save_as_pbm_with_threshold(path, 5, 1, &gradient, 0.5, false).unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(content, "P1\n5 1\n0 0 0 1 1\n");
+    }
+
+    #[test]
+    fn test_save_as_pbm_ends_with_newline() {
+        let path = std::env::temp_dir().join("byonn_test_pbm_partial_row.pbm");
+        let path = path.to_str().unwrap();
+
+        // The last row is short, but the file must still end with a newline
+        save_as_pbm(path, 2, 2, &[1.0, 0.0, 1.0]).unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_terminal_size_from_env() {
+        // The only test touching these variables, so the other tests cannot race with it
+        unsafe {
+            std::env::set_var("COLUMNS", "132");
+            std::env::set_var("LINES", "40");
+        }
+        assert_eq!(terminal_size(), (132, 40));
+
+        unsafe {
+            std::env::set_var("COLUMNS", "3");
+            std::env::set_var("LINES", "5");
+        }
+        assert_eq!(terminal_size(), (MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT));
+
+        unsafe {
+            std::env::set_var("COLUMNS", "wide");
+            std::env::remove_var("LINES");
+        }
+        assert_eq!(terminal_size(), (80, 24));
+
+        unsafe {
+            std::env::remove_var("COLUMNS");
+        }
+    }
+
+    #[test]
+    fn test_render_plot_tiny_size_does_not_panic() {
+        let points = || {
+            vec![Trace {
+                name: "Points".into(),
+                x: vec![0.0, 1.0],
+                y: vec![0.0, 1.0],
+                color: PlotColor::Cyan,
+                is_line: false,
+                hide_axes: false,
+            }]
+        };
+
+        // Narrower and shorter than the axis margins
+        render_plot(&points(), 5, 3, None, "Tiny".into());
+        render_dual_plots(&points(), &points(), 5, 3, None, "Tiny".into());
+    }
+
+    #[test]
+    fn test_pbm_binary_round_trip() {
+        // 10 columns, so every row needs a padded second byte
+        let (w, h) = (10, 3);
+        let pattern: Vec<f32> = (0..w * h)
+            .map(|i| ((i * 7) % 3 == 0) as u8 as f32)
+            .collect();
+
+        let plain = std::env::temp_dir().join("byonn_test_pbm_round_trip_plain.pbm");
+        let plain = plain.to_str().unwrap();
+        let binary = std::env::temp_dir().join("byonn_test_pbm_round_trip_binary.pbm");
+        let binary = binary.to_str().unwrap();
+
+        save_as_pbm(plain, w, h, &pattern).unwrap();
+        save_as_pbm_binary(binary, w, h, &pattern).unwrap();
+
+        let bytes = std::fs::read(binary).unwrap();
+        assert_eq!(bytes.len(), "P4\n10 3\n".len() + 2 * h);
+
+        let from_plain = read_pbm(plain).unwrap();
+        let from_binary = read_pbm(binary).unwrap();
+        assert_eq!(from_binary, (w, h, pattern));
+        assert_eq!(from_binary, from_plain);
+    }
+
+    fn read_bytes(name: &str, bytes: &[u8]) -> Result<(), ImageError> {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, bytes).unwrap();
+        read_pbm_for_nn(path.to_str().unwrap()).map(|_| ())
+    }
+
+    #[test]
+    fn test_read_pbm_for_nn_errors() {
+        let missing = std::env::temp_dir().join("byonn_test_no_such_image.pbm");
+        let result = read_pbm_for_nn(missing.to_str().unwrap());
+        assert!(matches!(result, Err(ImageError::Io(_))));
+
+        // (file name, contents, expected variant)
+        let cases: [(&str, &[u8], &str); 7] = [
+            ("bad_magic", b"P2\n2 2\n1 0 0 1\n", "BadMagic"),
+            ("bad_width", b"P1\nx 2\n1 0 0 1\n", "BadDimensions"),
+            ("zero_height", b"P1\n2 0\n", "BadDimensions"),
+            ("no_height", b"P1\n2", "UnexpectedEof"),
+            ("short_plain", b"P1\n2 2\n1 0 0\n", "UnexpectedEof"),
+            ("bad_pixel", b"P1\n2 1\n1 z\n", "BadPixel"),
+            ("short_binary", b"P4\n9 2\n\xff\x80\xff", "UnexpectedEof"),
+        ];
+
+        for (name, bytes, expected) in cases {
+            let err = read_bytes(&format!("byonn_test_pbm_{}.pbm", name), bytes).unwrap_err();
+            assert_eq!(format!("{:?}", err), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_read_pbm_for_nn_coordinates() {
+        let path = std::env::temp_dir().join("byonn_test_pbm_for_nn.pbm");
+        std::fs::write(&path, "P1\n# comment\n3 2\n1 0 1\n0 1 0\n").unwrap();
+
+        let (w, h, x, y) = read_pbm_for_nn(path.to_str().unwrap()).unwrap();
+        assert_eq!((w, h), (3, 2));
+        assert_eq!(y, vec![1.0, 0.0, 1.0, 0.0, 1.0, 0.0]);
+        assert_eq!(&x[6..8], &[1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_read_pbm_packed_digit_rows() {
+        let spaced = std::env::temp_dir().join("byonn_test_pbm_spaced_rows.pbm");
+        std::fs::write(&spaced, "P1\n4 3\n1 0 1 1\n0 0 1 0\n1 1 1 0\n").unwrap();
+        let packed = std::env::temp_dir().join("byonn_test_pbm_packed_rows.pbm");
+        std::fs::write(&packed, "P1\n4 3\n1011\n0010 # row comment\n1 1 10\n").unwrap();
+
+        let expected = read_pbm_for_nn(spaced.to_str().unwrap()).unwrap();
+        assert_eq!(
+            expected.3,
+            vec![1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0]
+        );
+
+        let packed = packed.to_str().unwrap();
+        assert_eq!(read_pbm_for_nn(packed).unwrap(), expected);
+        assert_eq!(read_pbm_for_nn_streaming(packed).unwrap(), expected);
+
+        // Anything besides 0/1 digits makes the token a single (here invalid) number
+        let bad = std::env::temp_dir().join("byonn_test_pbm_packed_bad.pbm");
+        std::fs::write(&bad, "P1\n3 1\n10x\n").unwrap();
+        let result = read_pbm_for_nn(bad.to_str().unwrap());
+        assert!(matches!(result, Err(ImageError::BadPixel)));
+    }
+
+    #[test]
+    fn test_read_pbm_for_nn_streaming_matches() {
+        let plain = std::env::temp_dir().join("byonn_test_pbm_streaming_plain.pbm");
+        std::fs::write(&plain, "P1\n# comment\n10 3 # trailing\n").unwrap();
+        let pattern: Vec<f32> = (0..30).map(|i| ((i * 7) % 3 == 0) as u8 as f32).collect();
+        let binary = std::env::temp_dir().join("byonn_test_pbm_streaming_binary.pbm");
+        save_as_pbm_binary(binary.to_str().unwrap(), 10, 3, &pattern).unwrap();
+
+        let mut text = std::fs::read_to_string(&plain).unwrap();
+        for row in pattern.chunks(10) {
+            let row: Vec<String> = row.iter().map(|p| p.to_string()).collect();
+            text.push_str(&row.join(" "));
+            text.push_str("#c\n");
+        }
+        std::fs::write(&plain, text).unwrap();
+
+        for path in [plain, binary] {
+            let path = path.to_str().unwrap();
+            let streamed = read_pbm_for_nn_streaming(path).unwrap();
+            assert_eq!(streamed, read_pbm_for_nn(path).unwrap(), "{}", path);
+            assert_eq!(streamed.3, pattern);
+        }
+
+        let short = std::env::temp_dir().join("byonn_test_pbm_streaming_short.pbm");
+        std::fs::write(&short, b"P4\n9 2\n\xff\x80\xff").unwrap();
+        let result = read_pbm_for_nn_streaming(short.to_str().unwrap());
+        assert!(matches!(result, Err(ImageError::UnexpectedEof)));
+    }
+
+    #[test]
+    fn test_format_signed_matches_printed_sign() {
+        let green = PlotColor::Green.to_ansi();
+        let red = PlotColor::Red.to_ansi();
+        let reset = PlotColor::Reset.to_ansi();
+
+        // Rounds to zero, so it must not print as a red "-0.000"
+        assert_eq!(
+            format_signed(-0.0001, 7, 3, Palette::Standard),
+            format!("{}  0.000{}", green, reset)
+        );
+        assert_eq!(
+            format_signed(-0.0, 7, 3, Palette::Standard),
+            format!("{}  0.000{}", green, reset)
+        );
+        assert_eq!(
+            format_signed(-0.25, 6, 2, Palette::Standard),
+            format!("{} -0.25{}", red, reset)
+        );
+        assert_eq!(
+            format_signed(1.5, 6, 2, Palette::Standard),
+            format!("{}  1.50{}", green, reset)
+        );
+    }
+
+    #[test]
+    fn test_format_signed_uses_palette() {
+        let reset = PlotColor::Reset.to_ansi();
+        let safe = Palette::ColorBlindSafe;
+
+        assert_eq!(
+            format_signed(0.5, 6, 2, safe),
+            format!("{}  0.50{}", PlotColor::Green.to_ansi_in(safe), reset)
+        );
+        assert_eq!(
+            format_signed(-0.5, 6, 2, safe),
+            format!("{} -0.50{}", PlotColor::Red.to_ansi_in(safe), reset)
+        );
+    }
+
+    #[test]
+    fn test_save_frame_pbm_names() {
+        let dir = std::env::temp_dir().join("byonn_test_frames");
+        let _ = std::fs::remove_dir_all(&dir);
+        let dir = dir.to_str().unwrap();
+
+        let first = save_frame_pbm(dir, 0, 3, 2, &[1.0, 0.0, 1.0, 0.0, 1.0, 0.0]).unwrap();
+        let later = save_frame_pbm(dir, 12, 3, 2, &[0.0; 6]).unwrap();
+
+        assert!(first.ends_with("frame_00000.pbm"));
+        assert!(later.ends_with("frame_00012.pbm"));
+        assert_eq!(std::fs::read_dir(dir).unwrap().count(), 2);
+
+        let (w, h, pixels) = read_pbm(&first).unwrap();
+        assert_eq!((w, h), (3, 2));
+        assert_eq!(pixels, vec![1.0, 0.0, 1.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_encode_gif_layout() {
+        let path = std::env::temp_dir().join("byonn_test_frames.gif");
+        let path = path.to_str().unwrap();
+        let frames = vec![vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0], vec![0.0; 6]];
+
+        encode_gif(&frames, 3, 2, path).unwrap();
+        let bytes = std::fs::read(path).unwrap();
+
+        assert_eq!(&bytes[..6], b"GIF89a");
+        assert_eq!(&bytes[6..10], &[3, 0, 2, 0]);
+        assert_eq!(bytes.last(), Some(&0x3B));
+
+        let short = vec![vec![0.0; 5]];
+        assert!(encode_gif(&short, 3, 2, path).is_err());
+    }
+
+    #[test]
+    fn test_palette_ansi_codes() {
+        let standard = [PlotColor::Green, PlotColor::Red, PlotColor::Cyan]
+            .map(|c| c.to_ansi_in(Palette::Standard));
+        assert_eq!(standard, ["\x1b[32m", "\x1b[31m", "\x1b[36m"]);
+
+        // Only the red/green pair changes, to blue and orange
+        let safe = [PlotColor::Green, PlotColor::Red, PlotColor::Cyan]
+            .map(|c| c.to_ansi_in(Palette::ColorBlindSafe));
+        assert_eq!(safe, ["\x1b[94m", "\x1b[38;5;208m", "\x1b[36m"]);
+
+        assert_eq!(Palette::from_name("standard"), Some(Palette::Standard));
+        assert_eq!(
+            Palette::from_name("colorblind"),
+            Some(Palette::ColorBlindSafe)
+        );
+        assert_eq!(Palette::from_name("sepia"), None);
+    }
+
+    #[test]
+    fn test_half_block_lines() {
+        // 3 rows need two character rows, the last one only uses its upper half
+        let data = [1.0, 0.0, 1.0, 1.0, 1.0, 0.0];
+        let lines = render_image_lines(2, 3, &data, RenderStyle::HalfBlock, 0.5);
+        assert_eq!(lines, vec!["█▄".to_string(), "▀ ".to_string()]);
+
+        let tall = vec![1.0; 4 * 7];
+        let half = render_image_lines(4, 7, &tall, RenderStyle::HalfBlock, 0.5);
+        assert_eq!(half.len(), 7_usize.div_ceil(2));
+        let braille = render_image_lines(4, 7, &tall, RenderStyle::Braille, 0.5);
+        assert_eq!(braille.len(), 7_usize.div_ceil(4));
+    }
+}
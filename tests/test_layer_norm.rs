@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use build_your_own_nn::{
+        Layer,
+        layer_norm::LayerNorm,
+        tensor::{Tensor, TensorError},
+    };
+
+    #[test]
+    fn test_layer_norm_rows_are_standardized() -> Result<(), TensorError> {
+        let x = Tensor::new(
+            vec![1.0, 2.0, 3.0, 4.0, -50.0, 0.0, 50.0, 100.0],
+            vec![2, 4],
+        )?;
+        let mut norm = LayerNorm::new(4);
+
+        // gamma = 1 and beta = 0, so the output is the normalized input
+        let y = norm.forward(&x)?;
+        assert_eq!(y, norm.forward_infer(&x)?);
+        for row in y.rows() {
+            let mean = row.iter().sum::<f32>() / 4.0;
+            let var = row.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / 4.0;
+            assert!(mean.abs() < 1e-5);
+            assert!((var - 1.0).abs() < 1e-3);
+        }
+
+        assert_eq!(norm.num_params(), 8);
+        assert_eq!(
+            norm.forward(&Tensor::new(vec![0.0; 3], vec![1, 3])?),
+            Err(TensorError::ShapeMismatch)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_layer_norm_input_gradient_matches_finite_difference() -> Result<(), TensorError> {
+        let x = Tensor::new(vec![0.5, -1.0, 2.0, 1.5, 0.0, -0.5], vec![2, 3])?;
+        let err = Tensor::new(vec![1.0, -2.0, 0.5, 0.25, 1.0, -1.0], vec![2, 3])?;
+        let mut norm = LayerNorm::new(3);
+        norm.set_params(vec![
+            Tensor::new(vec![1.5, -0.5, 2.0], vec![3])?,
+            Tensor::new(vec![0.1, 0.2, 0.3], vec![3])?,
+        ])?;
+
+        // Loss = sum(err * y), so dL/dx is exactly what backward returns for `err`
+        let loss = |input: &Tensor| -> Result<f32, TensorError> {
+            Ok(norm.forward_infer(input)?.mul(&err)?.sum(None)?.data()[0])
+        };
+
+        let h = 1e-2;
+        let mut numeric = Vec::new();
+        for i in 0..x.data().len() {
+            let mut plus = x.data().to_vec();
+            let mut minus = x.data().to_vec();
+            plus[i] += h;
+            minus[i] -= h;
+            let plus = loss(&Tensor::new(plus, vec![2, 3])?)?;
+            let minus = loss(&Tensor::new(minus, vec![2, 3])?)?;
+            numeric.push((plus - minus) / (2.0 * h));
+        }
+
+        norm.forward(&x)?;
+        let analytic = norm.compute_gradients(&err)?;
+        for (a, n) in analytic.data().iter().zip(&numeric) {
+            assert!((a - n).abs() < 1e-2, "analytic {} vs numeric {}", a, n);
+        }
+        Ok(())
+    }
+}
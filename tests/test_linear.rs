@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use build_your_own_nn::{
+        DefaultRng, Layer,
+        linear::{DEFAULT_INIT, InitScheme, Linear},
+        tensor::{Tensor, TensorError},
+    };
+
+    fn max_abs_weight(layer: &Linear) -> f32 {
+        layer
+            .weight()
+            .data()
+            .iter()
+            .fold(0.0, |m, w| m.max(w.abs()))
+    }
+
+    #[test]
+    fn test_default_init_is_kaiming_uniform() {
+        assert_eq!(
+            DEFAULT_INIT,
+            InitScheme::KaimingUniform {
+                negative_slope: 0.0
+            }
+        );
+
+        let fan_in = 24;
+        let layer = Linear::new(fan_in, 64, &mut DefaultRng::new(17));
+        let bound = (6.0 / fan_in as f32).sqrt();
+
+        // Every weight is inside the bound, and with this many draws some get close to it
+        let max = max_abs_weight(&layer);
+        assert!(max <= bound);
+        assert!(max > 0.9 * bound);
+    }
+
+    #[test]
+    fn test_kaiming_negative_slope_and_uniform_bounds() {
+        let init = InitScheme::KaimingUniform {
+            negative_slope: 1.0,
+        };
+        let layer = Linear::with_init(12, 64, init, &mut DefaultRng::new(17));
+        assert!(max_abs_weight(&layer) <= (6.0_f32 / (2.0 * 12.0)).sqrt());
+
+        let uniform = Linear::new_uniform(12, 64, &mut DefaultRng::new(17));
+        assert!(max_abs_weight(&uniform) > 0.9);
+        assert!(max_abs_weight(&uniform) <= 1.0);
+    }
+
+    #[test]
+    fn test_no_bias_is_pure_matmul() -> Result<(), TensorError> {
+        let mut layer = Linear::new_no_bias(3, 2, &mut DefaultRng::new(4));
+        let x = Tensor::new(vec![1.0, -2.0, 0.5, 3.0, 0.0, 1.0], vec![2, 3])?;
+
+        assert_eq!(layer.forward(&x)?, x.matmul(layer.weight())?);
+        assert_eq!(layer.num_params(), 6);
+        assert_eq!(layer.bias(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bias_is_added_and_trained() -> Result<(), TensorError> {
+        let mut layer = Linear::with_bias(3, 2, &mut DefaultRng::new(4));
+        assert_eq!(layer.num_params(), 8);
+
+        layer.set_params(vec![
+            layer.weight().clone(),
+            Tensor::new(vec![1.0, -1.0], vec![2])?,
+        ])?;
+        let x = Tensor::new(vec![1.0, -2.0, 0.5, 3.0, 0.0, 1.0], vec![2, 3])?;
+        let plain = x.matmul(layer.weight())?;
+        let y = layer.forward(&x)?;
+        for (row, plain_row) in y.rows().zip(plain.rows()) {
+            assert_eq!(row, &[plain_row[0] + 1.0, plain_row[1] - 1.0]);
+        }
+
+        // The bias moves against the error summed over the batch
+        let err = Tensor::new(vec![1.0, 0.0, 2.0, -1.0], vec![2, 2])?;
+        layer.backward(&err, 0.1)?;
+        let bias = layer.bias().unwrap().data();
+        assert!((bias[0] - 0.7).abs() < 1e-6);
+        assert!((bias[1] + 0.9).abs() < 1e-6);
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use build_your_own_nn::{loss::{l1_loss, mse_loss}, tensor::{Tensor, TensorError}};
+    use build_your_own_nn::{activation::stable_sigmoid, loss::{bce_sigmoid_delta, bce_weighted_delta, bce_with_logits_delta, bce_loss_per_sample, l1_loss, l1_loss_kahan, mse_loss, mse_loss_kahan, mse_loss_per_sample, softmax_cross_entropy_delta, softmax_cross_entropy_delta_from_labels, softmax_cross_entropy_loss, softmax_cross_entropy_loss_from_labels}, tensor::{Tensor, TensorError}};
 
     fn create_tensor(data: Vec<f32>, shape: Vec<usize>) -> Tensor {
         Tensor::new(data, shape).unwrap()
@@ -51,4 +51,116 @@ mod tests {
         let loss = mse_loss(&pred, &actual).unwrap();
         assert_eq!(loss.data()[0], 0.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bce_weighted_delta() {
+        let pred = create_tensor(vec![0.2, 0.7, 0.4, 0.9], vec![4, 1]);
+        let actual = create_tensor(vec![1.0, 0.0, 1.0, 0.0], vec![4, 1]);
+
+        let plain = bce_sigmoid_delta(&pred, &actual).unwrap();
+        let unit = bce_weighted_delta(&pred, &actual, 1.0).unwrap();
+        assert_eq!(unit.data(), plain.data());
+
+        // Positive rows double, negative rows stay as they were
+        let doubled = bce_weighted_delta(&pred, &actual, 2.0).unwrap();
+        assert_eq!(doubled.data()[0], 2.0 * plain.data()[0]);
+        assert_eq!(doubled.data()[1], plain.data()[1]);
+        assert_eq!(doubled.data()[2], 2.0 * plain.data()[2]);
+        assert_eq!(doubled.data()[3], plain.data()[3]);
+    }
+
+    #[test]
+    fn test_kahan_losses_match_plain_losses() {
+        let pred = create_tensor(vec![2.0, 4.0], vec![2, 1]);
+        let actual = create_tensor(vec![1.0, 6.0], vec![2, 1]);
+
+        assert_eq!(mse_loss_kahan(&pred, &actual).unwrap().data()[0], 2.5);
+        assert_eq!(l1_loss_kahan(&pred, &actual).unwrap().data()[0], 1.5);
+    }
+
+    #[test]
+    fn test_bce_with_logits_delta() {
+        let logits = create_tensor(vec![-120.0, -2.0, 0.0, 3.5, 120.0], vec![5, 1]);
+        let actual = create_tensor(vec![0.0, 1.0, 1.0, 0.0, 1.0], vec![5, 1]);
+
+        let fused = bce_with_logits_delta(&logits, &actual).unwrap();
+        let manual = bce_sigmoid_delta(&stable_sigmoid(&logits).unwrap(), &actual).unwrap();
+
+        for (f, m) in fused.data().iter().zip(manual.data()) {
+            assert!(f.is_finite());
+            assert!((f - m).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_mse_loss_per_sample() {
+        // Row errors: (1, 1) -> 1.0, (0, 0) -> 0.0, (2, -2) -> 4.0
+        let pred = create_tensor(vec![1.0, 2.0, 3.0, 4.0, 7.0, 4.0], vec![3, 2]);
+        let actual = create_tensor(vec![0.0, 1.0, 3.0, 4.0, 5.0, 6.0], vec![3, 2]);
+
+        let per_sample = mse_loss_per_sample(&pred, &actual).unwrap();
+        assert_eq!(per_sample.shape(), &[3]);
+        assert_eq!(per_sample.data(), &[1.0, 0.0, 4.0]);
+
+        let mean = per_sample.data().iter().sum::<f32>() / 3.0;
+        assert!((mean - mse_loss(&pred, &actual).unwrap().data()[0]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bce_loss_per_sample() {
+        let pred = create_tensor(vec![0.5, 0.9, 0.1], vec![3, 1]);
+        let actual = create_tensor(vec![1.0, 1.0, 1.0], vec![3, 1]);
+
+        let per_sample = bce_loss_per_sample(&pred, &actual).unwrap();
+        let expected = [0.5f32.ln(), 0.9f32.ln(), 0.1f32.ln()];
+        for (l, e) in per_sample.data().iter().zip(expected) {
+            assert!((l + e).abs() < 1e-5);
+        }
+
+        // A confident wrong answer stays finite
+        let wrong = bce_loss_per_sample(&create_tensor(vec![0.0], vec![1, 1]), &create_tensor(vec![1.0], vec![1, 1])).unwrap();
+        assert!(wrong.data()[0].is_finite());
+    }
+
+    #[test]
+    fn test_losses_on_zero_sized_tensors() {
+        // No samples means no loss, rather than 0 / 0
+        let empty = create_tensor(vec![], vec![0]);
+        assert_eq!(mse_loss(&empty, &empty).unwrap().data(), &[0.0]);
+        assert_eq!(l1_loss(&empty, &empty).unwrap().data(), &[0.0]);
+
+        let no_cols = create_tensor(vec![], vec![2, 0]);
+        assert_eq!(mse_loss(&no_cols, &no_cols).unwrap().data(), &[0.0]);
+        assert_eq!(mse_loss_per_sample(&no_cols, &no_cols).unwrap().data(), &[0.0, 0.0]);
+        assert_eq!(bce_loss_per_sample(&no_cols, &no_cols).unwrap().data(), &[0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_softmax_cross_entropy_labels_match_one_hot() {
+        let logits = create_tensor(vec![
+            2.0, 1.0, 0.1, -1.0,
+            0.5, 0.5, 3.0, 0.0,
+            -2.0, 4.0, 1.0, 1.0,
+        ], vec![3, 4]);
+        let labels = [0, 2, 3];
+        let one_hot = create_tensor(vec![
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ], vec![3, 4]);
+
+        let loss = softmax_cross_entropy_loss(&logits, &one_hot).unwrap();
+        let loss_labels = softmax_cross_entropy_loss_from_labels(&logits, &labels).unwrap();
+        assert!((loss.data()[0] - loss_labels.data()[0]).abs() < 1e-6);
+
+        let delta = softmax_cross_entropy_delta(&logits, &one_hot).unwrap();
+        let delta_labels = softmax_cross_entropy_delta_from_labels(&logits, &labels).unwrap();
+        assert_eq!(delta_labels.shape(), &[3, 4]);
+        for (a, b) in delta.data().iter().zip(delta_labels.data()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        assert_eq!(softmax_cross_entropy_loss_from_labels(&logits, &[0, 4, 1]).err(), Some(TensorError::IndexOutOfBounds));
+        assert_eq!(softmax_cross_entropy_delta_from_labels(&logits, &[0, 1]).err(), Some(TensorError::ShapeMismatch));
+    }
+}
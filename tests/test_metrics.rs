@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use build_your_own_nn::{
+        metrics::mape,
+        tensor::{Tensor, TensorError},
+    };
+
+    #[test]
+    fn test_mape_perfect_prediction() -> Result<(), TensorError> {
+        let actual = Tensor::new(vec![5.6, 6.6, 9.5], vec![3, 1])?;
+
+        assert_eq!(mape(&actual, &actual)?, 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mape_known_error() -> Result<(), TensorError> {
+        // 10% and 30% off, while the zero target is skipped
+        let predicted = Tensor::new(vec![110.0, 14.0, 3.0], vec![3])?;
+        let actual = Tensor::new(vec![100.0, 20.0, 0.0], vec![3])?;
+        assert!((mape(&predicted, &actual)? - 20.0).abs() < 1e-4);
+
+        let zeros = Tensor::new(vec![0.0, 0.0], vec![2])?;
+        assert_eq!(mape(&zeros, &zeros)?, 0.0);
+
+        let short = Tensor::new(vec![1.0], vec![1])?;
+        assert_eq!(mape(&short, &actual), Err(TensorError::ShapeMismatch));
+        Ok(())
+    }
+}
@@ -0,0 +1,696 @@
+#[cfg(test)]
+mod tests {
+    use build_your_own_nn::{
+        DefaultRng,
+        activation::{Activation, ActivationType},
+        data::shuffle_rows,
+        linear::{DEFAULT_INIT, InitScheme, Linear},
+        loss::{bce_loss, bce_sigmoid_delta, mse_loss, mse_loss_gradient},
+        neural_network::{LrSchedule, Network, NetworkBuilder},
+        tensor::{Tensor, TensorError},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_predict_matches_forward() -> Result<(), TensorError> {
+        let mut rng = DefaultRng::new(42);
+        let mut nn = NetworkBuilder::new()
+            .add_layer(Box::new(Linear::new(3, 4, &mut rng)))
+            .add_layer(Box::new(Activation::new(ActivationType::Tanh)))
+            .add_layer(Box::new(Linear::new(4, 1, &mut rng)))
+            .add_layer(Box::new(Activation::new(ActivationType::Sigmoid)))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        let input = Tensor::new(vec![0.0, 1.0, 1.0, 1.0, 0.0, 1.0], vec![2, 3])?;
+        let forward_out = nn.forward(input.clone())?;
+
+        let shared = &nn;
+        let first = shared.predict(&input)?;
+        let second = shared.predict(&input)?;
+
+        assert_eq!(first, forward_out);
+        assert_eq!(first, second);
+        Ok(())
+    }
+
+    #[test]
+    fn test_freeze_up_to_trains_only_the_head() -> Result<(), TensorError> {
+        let build = || {
+            let mut rng = DefaultRng::new(13);
+            NetworkBuilder::new()
+                .dense(2, 4, ActivationType::Tanh, &mut rng)
+                .dense(4, 1, ActivationType::Sigmoid, &mut rng)
+                .loss_gradient(mse_loss_gradient)
+                .build()
+                .unwrap()
+        };
+        let x = Tensor::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0], vec![4, 2])?;
+        let y = Tensor::new(vec![0.0, 1.0, 1.0, 0.0], vec![4, 1])?;
+
+        let mut cached = build();
+        let mut uncached = build();
+        let before: Vec<Tensor> = cached.params().into_iter().cloned().collect();
+
+        cached.freeze_up_to(2)?;
+        uncached.freeze_up_to(2)?;
+        uncached.set_frozen_cache(false);
+
+        let cached_history = cached.fit_with_history(&x, &y, 30, 0.5)?;
+        let uncached_history = uncached.fit_with_history(&x, &y, 30, 0.5)?;
+
+        assert_eq!(cached_history, uncached_history);
+        assert_eq!(cached.params(), uncached.params());
+        assert_eq!(cached.params()[0], &before[0]);
+        assert_ne!(cached.params()[1], &before[1]);
+
+        assert_eq!(cached.freeze_up_to(5), Err(TensorError::IndexOutOfBounds));
+        Ok(())
+    }
+
+    #[test]
+    fn test_layer_by_name() {
+        let mut rng = DefaultRng::new(10);
+        let nn = NetworkBuilder::new()
+            .add_named_layer("encoder", Box::new(Linear::new(2, 3, &mut rng)))
+            .add_layer(Box::new(Activation::new(ActivationType::Tanh)))
+            .add_named_layer("head", Box::new(Linear::new(3, 1, &mut rng)))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        let encoder = nn.layer_by_name("encoder").unwrap();
+        assert_eq!(encoder.params()[0].shape(), &[2, 3]);
+        assert_eq!(nn.layer_by_name("head").unwrap().num_params(), 3);
+        // Unnamed layers are called after their index
+        assert_eq!(nn.layer_by_name("layer_1").unwrap().num_params(), 0);
+
+        assert!(nn.layer_by_name("decoder").is_none());
+        assert!(nn.layer_by_name("layer_3").is_none());
+    }
+
+    #[test]
+    fn test_layer_mut_sets_weights() -> Result<(), TensorError> {
+        let mut nn = NetworkBuilder::new()
+            .add_layer(Box::new(Linear::new(2, 2, &mut DefaultRng::new(9))))
+            .add_layer(Box::new(Activation::new(ActivationType::ReLU)))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        let weights = Tensor::new(vec![1.0, -1.0, 2.0, 0.5], vec![2, 2])?;
+        nn.layer_mut(0).unwrap().set_params(vec![weights.clone()])?;
+        assert!(nn.layer_mut(2).is_none());
+
+        let input = Tensor::new(vec![1.0, 1.0, -1.0, 2.0], vec![2, 2])?;
+        // [1, 1] -> [3, -0.5] -> [3, 0] and [-1, 2] -> [3, 2]
+        assert_eq!(nn.predict(&input)?.data(), &[3.0, 0.0, 3.0, 2.0]);
+        assert_eq!(nn.params(), vec![&weights]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_spec_builds_dense_stack() -> Result<(), TensorError> {
+        let spec = [
+            (4, ActivationType::Tanh),
+            (3, ActivationType::ReLU),
+            (1, ActivationType::Sigmoid),
+        ];
+        let mut rng = DefaultRng::new(11);
+        let nn = NetworkBuilder::from_spec(&spec, 2, &mut rng)
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        assert_eq!(nn.num_layers(), 6);
+        let shapes: Vec<&[usize]> = nn.params().iter().map(|p| p.shape()).collect();
+        assert_eq!(shapes, vec![&[2, 4][..], &[4, 3], &[3, 1]]);
+
+        // Same seed through dense calls, so any activation out of order changes the output
+        let mut rng = DefaultRng::new(11);
+        let manual = NetworkBuilder::new()
+            .dense(2, 4, ActivationType::Tanh, &mut rng)
+            .dense(4, 3, ActivationType::ReLU, &mut rng)
+            .dense(3, 1, ActivationType::Sigmoid, &mut rng)
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        let input = Tensor::new(vec![0.5, -1.0, 2.0, 0.25, -0.75, 1.5], vec![3, 2])?;
+        assert_eq!(nn.predict(&input)?, manual.predict(&input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_spec_with_init_uses_scheme() -> Result<(), TensorError> {
+        let spec = [(3, ActivationType::ReLU), (1, ActivationType::Sigmoid)];
+        let mut rng = DefaultRng::new(5);
+        let nn = NetworkBuilder::from_spec_with_init(&spec, 2, DEFAULT_INIT, &mut rng)
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        // `Linear::new` initializes with `DEFAULT_INIT` too, so the weights must match
+        let mut rng = DefaultRng::new(5);
+        let first = Linear::new(2, 3, &mut rng);
+        let second = Linear::new(3, 1, &mut rng);
+        assert_eq!(nn.params(), vec![first.weight(), second.weight()]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dense_builder_layer_order() -> Result<(), TensorError> {
+        let mut rng = DefaultRng::new(7);
+        let dense = NetworkBuilder::new()
+            .dense(2, 3, ActivationType::Tanh, &mut rng)
+            .dense(3, 1, ActivationType::Sigmoid, &mut rng)
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        // Same seed, same four layers spelled out by hand
+        let mut rng = DefaultRng::new(7);
+        let manual = NetworkBuilder::new()
+            .add_layer(Box::new(Linear::with_init(
+                2,
+                3,
+                InitScheme::Xavier,
+                &mut rng,
+            )))
+            .add_layer(Box::new(Activation::new(ActivationType::Tanh)))
+            .add_layer(Box::new(Linear::with_init(
+                3,
+                1,
+                InitScheme::Xavier,
+                &mut rng,
+            )))
+            .add_layer(Box::new(Activation::new(ActivationType::Sigmoid)))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        let input = Tensor::new(vec![0.5, -1.0, 2.0, 0.25], vec![2, 2])?;
+
+        assert_eq!(dense.predict(&input)?, manual.predict(&input)?);
+
+        // Four layers: Linear [2, 3], Tanh, Linear [3, 1], Sigmoid
+        assert_eq!(dense.num_layers(), 4);
+        let layer = |i: usize| dense.layer_by_name(&format!("layer_{}", i)).unwrap();
+        let weight_shape = |i: usize| layer(i).params().first().map(|p| p.shape().to_vec());
+        assert_eq!(weight_shape(0), Some(vec![2, 3]));
+        assert_eq!(weight_shape(2), Some(vec![3, 1]));
+
+        // The activations hold no parameters and apply their own function
+        let probe = Tensor::new(vec![-2.0, 0.0, 3.0], vec![1, 3])?;
+        let close = |a: Tensor, b: Tensor| {
+            a.data()
+                .iter()
+                .zip(b.data())
+                .all(|(x, y)| (x - y).abs() < 1e-6)
+        };
+        assert_eq!(layer(1).num_params(), 0);
+        assert!(close(layer(1).forward_infer(&probe)?, probe.tanh()?));
+        assert_eq!(layer(3).num_params(), 0);
+        assert!(close(layer(3).forward_infer(&probe)?, probe.sigmoid()?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_without_layers_errors() {
+        let result = NetworkBuilder::new()
+            .loss_gradient(mse_loss_gradient)
+            .build();
+        assert_eq!(
+            result.err().as_deref(),
+            Some("At least one layer is required")
+        );
+
+        let result = NetworkBuilder::new().build();
+        assert_eq!(
+            result.err().as_deref(),
+            Some("Loss gradient function is required")
+        );
+    }
+
+    #[test]
+    fn test_fit_with_validation_holds_out_rows() -> Result<(), TensorError> {
+        let build = || {
+            let mut rng = DefaultRng::new(5);
+            NetworkBuilder::new()
+                .add_layer(Box::new(Linear::new(2, 1, &mut rng)))
+                .loss_gradient(mse_loss_gradient)
+                .build()
+                .unwrap()
+        };
+
+        let x: Vec<f32> = (0..10).flat_map(|i| [i as f32 / 10.0, 1.0]).collect();
+        let y: Vec<f32> = (0..10).map(|i| 2.0 * i as f32 / 10.0 + 0.5).collect();
+        let x = Tensor::new(x, vec![10, 2])?;
+        let y = Tensor::new(y, vec![10, 1])?;
+
+        let mut validated = build();
+        let history =
+            validated.fit_with_validation(&x, &y, 50, 0.1, 0.2, &mut DefaultRng::new(9))?;
+        assert_eq!(history.len(), 50);
+
+        // Same shuffle, then train only on the first 8 rows: the held-out 2 rows
+        // must not have influenced the weights
+        let (sx, sy) = shuffle_rows(&x, &y, &mut DefaultRng::new(9))?;
+        let x_train = Tensor::new(sx.data()[..16].to_vec(), vec![8, 2])?;
+        let y_train = Tensor::new(sy.data()[..8].to_vec(), vec![8, 1])?;
+
+        let mut reference = build();
+        reference.fit(&x_train, &y_train, 50, 0.1)?;
+
+        assert_eq!(validated.predict(&x)?, reference.predict(&x)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fit_with_callback_stride() -> Result<(), TensorError> {
+        let mut rng = DefaultRng::new(3);
+        let mut nn = NetworkBuilder::new()
+            .add_layer(Box::new(Linear::new(2, 1, &mut rng)))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        let x = Tensor::new(vec![0.0, 1.0, 1.0, 1.0], vec![2, 2])?;
+        let y = Tensor::new(vec![1.0, 3.0], vec![2, 1])?;
+
+        let mut calls = Vec::new();
+        nn.fit_with_callback(&x, &y, 23, 0.05, 5, &mut |epoch, loss| {
+            calls.push((epoch, loss))
+        })?;
+
+        let epochs: Vec<usize> = calls.iter().map(|&(e, _)| e).collect();
+        assert_eq!(epochs, vec![5, 10, 15, 20]);
+        assert!(calls.iter().all(|&(_, loss)| loss.is_finite()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_layer_and_parameter_counts() {
+        let mut rng = DefaultRng::new(1);
+        let hl = 64;
+        let nn = NetworkBuilder::new()
+            .dense(2, hl, ActivationType::Tanh, &mut rng)
+            .dense(hl, hl, ActivationType::Tanh, &mut rng)
+            .dense(hl, 2 * hl, ActivationType::Tanh, &mut rng)
+            .dense(2 * hl, hl, ActivationType::Tanh, &mut rng)
+            .dense(hl, hl / 2, ActivationType::Tanh, &mut rng)
+            .dense(hl / 2, 1, ActivationType::Sigmoid, &mut rng)
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        assert_eq!(nn.num_layers(), 12);
+        // 2*64 + 64*64 + 64*128 + 128*64 + 64*32 + 32*1
+        assert_eq!(nn.num_parameters(), 22_688);
+    }
+
+    #[test]
+    fn test_fit_with_history_aborts_on_nan() -> Result<(), TensorError> {
+        let mut rng = DefaultRng::new(2);
+        let mut nn = NetworkBuilder::new()
+            .add_layer(Box::new(Linear::new(1, 1, &mut rng)))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        // Each step multiplies the weight error by about -2e6, so it overflows almost at once
+        let x = Tensor::new(vec![1000.0], vec![1, 1])?;
+        let y = Tensor::new(vec![1.0], vec![1, 1])?;
+
+        let result = nn.fit_with_history(&x, &y, 20, 1.0);
+        assert_eq!(result, Err(TensorError::NumericalInstability));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fit_with_report() -> Result<(), TensorError> {
+        let build = || {
+            NetworkBuilder::new()
+                .add_layer(Box::new(Linear::new(2, 1, &mut DefaultRng::new(4))))
+                .loss_gradient(mse_loss_gradient)
+                .build()
+                .unwrap()
+        };
+        let x = Tensor::new(vec![0.0, 1.0, 1.0, 1.0, 2.0, 1.0], vec![3, 2])?;
+        let y = Tensor::new(vec![1.0, 3.0, 5.0], vec![3, 1])?;
+
+        let report = build().fit_with_report(&x, &y, 25, 0.05)?;
+        let history = build().fit_with_history(&x, &y, 25, 0.05)?;
+
+        assert_eq!(report.epochs, 25);
+        assert_eq!(report.final_loss, history[24]);
+        assert!(report.elapsed > Duration::ZERO);
+        assert!(report.samples_per_sec > 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fit_with_history_records_losses() -> Result<(), TensorError> {
+        let mut rng = DefaultRng::new(2);
+        let mut nn = NetworkBuilder::new()
+            .add_layer(Box::new(Linear::new(2, 1, &mut rng)))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        let x = Tensor::new(vec![0.0, 1.0, 1.0, 1.0], vec![2, 2])?;
+        let y = Tensor::new(vec![1.0, 3.0], vec![2, 1])?;
+
+        let history = nn.fit_with_history(&x, &y, 200, 0.1)?;
+        assert_eq!(history.len(), 200);
+        assert!(history[199] < history[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_output_shape_through_stack() -> Result<(), TensorError> {
+        let mut rng = DefaultRng::new(4);
+        let nn = NetworkBuilder::new()
+            .add_layer(Box::new(Linear::new(3, 5, &mut rng)))
+            .add_layer(Box::new(Activation::new(ActivationType::ReLU)))
+            .add_layer(Box::new(Linear::new(5, 2, &mut rng)))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+
+        assert_eq!(nn.output_shape(&[7, 3])?, vec![7, 2]);
+        assert_eq!(nn.output_shape(&[3])?, vec![2]);
+
+        // Symbolic shapes agree with a real pass
+        let input = Tensor::new(vec![0.5; 21], vec![7, 3])?;
+        assert_eq!(nn.predict(&input)?.shape(), &nn.output_shape(&[7, 3])?[..]);
+
+        assert_eq!(nn.output_shape(&[7, 4]), Err(TensorError::ShapeMismatch));
+        assert_eq!(nn.output_shape(&[2, 7, 3]), Err(TensorError::InvalidRank));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fit_with_checkpoint_resumable() -> Result<(), Box<dyn std::error::Error>> {
+        let build = |seed| {
+            let mut rng = DefaultRng::new(seed);
+            NetworkBuilder::new()
+                .dense(2, 4, ActivationType::Tanh, &mut rng)
+                .dense(4, 1, ActivationType::Sigmoid, &mut rng)
+                .loss_gradient(mse_loss_gradient)
+                .build()
+                .unwrap()
+        };
+
+        let x = Tensor::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0], vec![4, 2])?;
+        let y = Tensor::new(vec![0.0, 1.0, 1.0, 0.0], vec![4, 1])?;
+
+        let path = std::env::temp_dir().join("byonn_test_checkpoint.txt");
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let mut nn = build(1);
+        nn.fit_with_checkpoint(&x, &y, 3, 0.5, 1, path)?;
+        assert!(std::path::Path::new(path).exists());
+
+        // Different initial weights, replaced by the checkpoint
+        let mut restored = build(99);
+        assert_ne!(restored.predict(&x)?, nn.predict(&x)?);
+        restored.load_weights(path)?;
+        assert_eq!(restored.predict(&x)?, nn.predict(&x)?);
+
+        // A checkpoint only fits the architecture it came from
+        let mut other = NetworkBuilder::new()
+            .dense(2, 3, ActivationType::Tanh, &mut DefaultRng::new(1))
+            .dense(3, 1, ActivationType::Sigmoid, &mut DefaultRng::new(2))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+        assert!(other.load_weights(path).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_weights_bad_last_layer_changes_nothing() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let path = std::env::temp_dir().join("byonn_test_checkpoint_bad_last.txt");
+        let path = path.to_str().unwrap();
+
+        let mut rng = DefaultRng::new(3);
+        let saved = NetworkBuilder::new()
+            .dense(2, 4, ActivationType::Tanh, &mut rng)
+            .dense(4, 1, ActivationType::Sigmoid, &mut rng)
+            .loss_gradient(mse_loss_gradient)
+            .build()?;
+        saved.save_weights(path)?;
+
+        // Same first layer shapes, only the last Linear differs
+        let mut rng = DefaultRng::new(8);
+        let mut target = NetworkBuilder::new()
+            .dense(2, 4, ActivationType::Tanh, &mut rng)
+            .dense(4, 2, ActivationType::Sigmoid, &mut rng)
+            .loss_gradient(mse_loss_gradient)
+            .build()?;
+        let before: Vec<Tensor> = target.params().into_iter().cloned().collect();
+
+        assert!(target.load_weights(path).is_err());
+        let after: Vec<Tensor> = target.params().into_iter().cloned().collect();
+        assert_eq!(before, after);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cosine_annealing_rate() {
+        let schedule = LrSchedule::CosineAnnealing {
+            t_max: 100,
+            eta_min: 0.01,
+        };
+
+        assert!((schedule.rate(0.5, 0) - 0.5).abs() < 1e-6);
+        assert!((schedule.rate(0.5, 50) - 0.255).abs() < 1e-6);
+        assert!((schedule.rate(0.5, 100) - 0.01).abs() < 1e-6);
+        // Climbs back towards the initial rate after t_max
+        assert!(schedule.rate(0.5, 150) > schedule.rate(0.5, 100));
+    }
+
+    #[test]
+    fn test_step_and_exponential_decay() {
+        let step = LrSchedule::StepDecay {
+            step_size: 10,
+            gamma: 0.5,
+        };
+        assert_eq!(step.rate(0.8, 9), 0.8);
+        assert_eq!(step.rate(0.8, 10), 0.4);
+        assert_eq!(step.rate(0.8, 25), 0.2);
+
+        let exponential = LrSchedule::ExponentialDecay { gamma: 0.5 };
+        assert_eq!(exponential.rate(0.8, 3), 0.1);
+    }
+
+    #[test]
+    fn test_fit_scheduled_constant_matches_fit() -> Result<(), TensorError> {
+        let build = || {
+            NetworkBuilder::new()
+                .add_layer(Box::new(Linear::new(2, 1, &mut DefaultRng::new(8))))
+                .loss_gradient(mse_loss_gradient)
+                .build()
+                .unwrap()
+        };
+        let x = Tensor::new(vec![0.0, 1.0, 1.0, 1.0], vec![2, 2])?;
+        let y = Tensor::new(vec![1.0, 3.0], vec![2, 1])?;
+
+        let mut plain = build();
+        plain.fit(&x, &y, 20, 0.1)?;
+        let mut scheduled = build();
+        scheduled.fit_scheduled(&x, &y, 20, 0.1, LrSchedule::Constant)?;
+
+        assert_eq!(scheduled.predict(&x)?, plain.predict(&x)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fit_per_layer_lr_freezes_zero_rate() -> Result<(), TensorError> {
+        let mut rng = DefaultRng::new(21);
+        let mut nn = NetworkBuilder::new()
+            .dense(2, 3, ActivationType::Tanh, &mut rng)
+            .add_layer(Box::new(Linear::new(3, 1, &mut rng)))
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+        let x = Tensor::new(vec![0.0, 1.0, 1.0, 1.0], vec![2, 2])?;
+        let y = Tensor::new(vec![1.0, 3.0], vec![2, 1])?;
+
+        let before: Vec<Tensor> = nn.params().into_iter().cloned().collect();
+        nn.fit_per_layer_lr(&x, &y, 10, &[0.0, 0.1])?;
+        let after = nn.params();
+
+        assert_eq!(after[0], &before[0]);
+        assert_ne!(after[1], &before[1]);
+
+        // The Tanh activation does not count as a trainable layer
+        assert_eq!(
+            nn.fit_per_layer_lr(&x, &y, 1, &[0.1, 0.1, 0.1]),
+            Err(TensorError::ShapeMismatch)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fit_clipped_caps_global_norm() -> Result<(), TensorError> {
+        let build = || {
+            let mut rng = DefaultRng::new(13);
+            NetworkBuilder::new()
+                .dense(2, 3, ActivationType::Tanh, &mut rng)
+                .add_layer(Box::new(Linear::new(3, 1, &mut rng)))
+                .loss_gradient(mse_loss_gradient)
+                .build()
+                .unwrap()
+        };
+        let x = Tensor::new(vec![0.0, 1.0, 1.0, 1.0], vec![2, 2])?;
+        let y = Tensor::new(vec![10.0, 30.0], vec![2, 1])?;
+
+        // Without an effective limit, the two-phase update matches the inline one
+        let mut unclipped = build();
+        unclipped.fit_clipped(&x, &y, 1, 0.01, f32::MAX)?;
+        let mut plain = build();
+        plain.fit(&x, &y, 1, 0.01)?;
+        assert_eq!(unclipped.predict(&x)?, plain.predict(&x)?);
+
+        let max_norm = 0.5;
+        assert!(unclipped.grad_norm() > max_norm);
+
+        let mut clipped = build();
+        clipped.fit_clipped(&x, &y, 1, 0.01, max_norm)?;
+        assert!((clipped.grad_norm() - max_norm).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_predict_class_binary_and_multiclass() -> Result<(), TensorError> {
+        let mut rng = DefaultRng::new(17);
+        let mut binary = NetworkBuilder::new()
+            .dense(2, 4, ActivationType::Tanh, &mut rng)
+            .dense(4, 1, ActivationType::Sigmoid, &mut rng)
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+        let x = Tensor::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0], vec![4, 2])?;
+        let y = Tensor::new(vec![0.0, 1.0, 1.0, 1.0], vec![4, 1])?;
+        binary.fit(&x, &y, 200, 0.5)?;
+
+        let proba = binary.predict_proba(&x)?;
+        let classes = binary.predict_class(&x, 0.5)?;
+        assert_eq!(classes.len(), 4);
+        for (&class, &p) in classes.iter().zip(proba.data()) {
+            assert_eq!(class, (p >= 0.5) as usize);
+        }
+
+        let multi = NetworkBuilder::new()
+            .dense(2, 3, ActivationType::Sigmoid, &mut rng)
+            .loss_gradient(mse_loss_gradient)
+            .build()
+            .unwrap();
+        let classes = multi.predict_class(&x, 0.5)?;
+        assert_eq!(classes.len(), 4);
+        assert!(classes.iter().all(|&c| c < 3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let mut nn = NetworkBuilder::new()
+            .add_layer(Box::new(Linear::new(2, 1, &mut DefaultRng::new(6))))
+            .loss_gradient(mse_loss_gradient)
+            .build()?;
+        let x = Tensor::new(vec![0.0, 1.0, 1.0, 1.0], vec![2, 2])?;
+        let y = Tensor::new(vec![1.0, 3.0], vec![2, 1])?;
+        let history = nn.fit_with_history(&x, &y, 25, 0.1)?;
+
+        let path = std::env::temp_dir().join("byonn_test_history.txt");
+        let path = path.to_str().unwrap();
+        Network::save_history(&history, path)?;
+        let loaded = Network::load_history(path)?;
+
+        assert_eq!(loaded.len(), history.len());
+        for (a, b) in loaded.iter().zip(&history) {
+            assert!((a - b).abs() <= 1e-6 * b.abs());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_loss_sums_weighted_deltas() -> Result<(), TensorError> {
+        let build = |extra_weight: Option<f32>| {
+            let builder = NetworkBuilder::new()
+                .add_layer(Box::new(Linear::new(2, 1, &mut DefaultRng::new(12))))
+                .loss_gradient(mse_loss_gradient);
+            match extra_weight {
+                Some(w) => builder.add_loss(w, mse_loss_gradient),
+                None => builder,
+            }
+            .build()
+            .unwrap()
+        };
+        let predicted = Tensor::new(vec![0.5, 2.0, -1.0], vec![3, 1])?;
+        let actual = Tensor::new(vec![1.0, 1.0, 0.0], vec![3, 1])?;
+
+        let single = build(None);
+        let doubled = build(Some(1.0));
+        let single_delta = single.loss_delta(&predicted, &actual)?;
+        assert_eq!(single_delta, mse_loss_gradient(&predicted, &actual)?);
+        assert_eq!(
+            doubled.loss_delta(&predicted, &actual)?,
+            single_delta.scale(&2.0)?
+        );
+
+        // Doubling the delta of a linear layer is the same step as doubling the rate
+        let (mut single, mut doubled) = (single, doubled);
+        let x = Tensor::new(vec![0.0, 1.0, 1.0, 1.0, 2.0, 1.0], vec![3, 2])?;
+        single.fit(&x, &actual, 5, 0.02)?;
+        doubled.fit(&x, &actual, 5, 0.01)?;
+        assert_eq!(single.params(), doubled.params());
+        Ok(())
+    }
+
+    #[test]
+    fn test_loss_grad_fn_accessor() -> Result<(), TensorError> {
+        let nn = NetworkBuilder::new()
+            .add_layer(Box::new(Activation::new(ActivationType::Sigmoid)))
+            .loss_gradient(bce_sigmoid_delta)
+            .build()
+            .unwrap();
+        let predicted = Tensor::new(vec![0.75, 0.25], vec![2, 1])?;
+        let actual = Tensor::new(vec![1.0, 0.0], vec![2, 1])?;
+
+        let delta = (nn.loss_grad_fn())(&predicted, &actual)?;
+        assert_eq!(delta, Tensor::new(vec![-0.125, 0.125], vec![2, 1])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reported_loss_follows_builder_loss() -> Result<(), TensorError> {
+        let build = |with_bce: bool| {
+            let builder = NetworkBuilder::new()
+                .dense(2, 1, ActivationType::Sigmoid, &mut DefaultRng::new(6))
+                .loss_gradient(bce_sigmoid_delta);
+            match with_bce {
+                true => builder.loss(bce_loss).build().unwrap(),
+                false => builder.build().unwrap(),
+            }
+        };
+        let x = Tensor::new(vec![0.0, 1.0, 1.0, 0.0, 1.0, 1.0], vec![3, 2])?;
+        let y = Tensor::new(vec![1.0, 0.0, 1.0], vec![3, 1])?;
+
+        // The first history entry is the loss of the untrained network's forward pass
+        let mut bce_nn = build(true);
+        let expected = bce_loss(&bce_nn.predict(&x)?, &y)?.data()[0];
+        assert_eq!(bce_nn.fit_with_history(&x, &y, 2, 0.1)?[0], expected);
+
+        // Without `loss`, MSE is reported
+        let mut mse_nn = build(false);
+        let expected = mse_loss(&mse_nn.predict(&x)?, &y)?.data()[0];
+        assert_eq!(mse_nn.fit_with_history(&x, &y, 2, 0.1)?[0], expected);
+        Ok(())
+    }
+}
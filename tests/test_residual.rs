@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use build_your_own_nn::{
+        DefaultRng, Layer,
+        linear::Linear,
+        residual::Residual,
+        tensor::{Tensor, TensorError},
+    };
+
+    fn linear(weight: &Tensor) -> Linear {
+        let mut layer = Linear::new_uniform(2, 2, &mut DefaultRng::new(1));
+        layer.set_weight(weight.clone());
+        layer
+    }
+
+    #[test]
+    fn test_residual_forward_backward() -> Result<(), TensorError> {
+        let w = Tensor::new(vec![1.0, 2.0, -1.0, 0.5], vec![2, 2])?;
+        let x = Tensor::new(vec![1.0, 2.0, -3.0, 0.5], vec![2, 2])?;
+        let err = Tensor::new(vec![0.5, -1.0, 2.0, 1.0], vec![2, 2])?;
+        let lr = 0.1;
+
+        let mut residual = Residual::new(Box::new(linear(&w)));
+        let mut plain = linear(&w);
+
+        // y = xW + x
+        let y = residual.forward(&x)?;
+        assert_eq!(y, plain.forward(&x)?.add(&x)?);
+
+        // dL/dx = err W^T + err, and the inner weights get the plain Linear update
+        let input_error = residual.backward(&err, lr)?;
+        assert_eq!(input_error.data(), &[-1.0, -2.0, 6.0, -0.5]);
+        assert_eq!(input_error, plain.backward(&err, lr)?.add(&err)?);
+        assert_eq!(residual.params()[0], plain.weight());
+        Ok(())
+    }
+
+    #[test]
+    fn test_residual_requires_matching_shapes() -> Result<(), TensorError> {
+        let widening = Residual::new(Box::new(Linear::new(2, 3, &mut DefaultRng::new(2))));
+
+        assert_eq!(
+            widening.output_shape(&[4, 2]),
+            Err(TensorError::ShapeMismatch)
+        );
+        let x = Tensor::new(vec![1.0, 2.0], vec![1, 2])?;
+        assert_eq!(widening.forward_infer(&x), Err(TensorError::ShapeMismatch));
+        Ok(())
+    }
+}
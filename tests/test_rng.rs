@@ -0,0 +1,20 @@
+#[cfg(test)]
+mod tests {
+    use build_your_own_nn::{DefaultRng, Rng};
+
+    #[test]
+    fn test_default_rng_is_reproducible() {
+        let mut first = DefaultRng::new(42);
+        let mut second = DefaultRng::new(42);
+        let a: Vec<i32> = (0..16).map(|_| first.next_u32()).collect();
+        let b: Vec<i32> = (0..16).map(|_| second.next_u32()).collect();
+        assert_eq!(a, b);
+
+        let mut other = DefaultRng::new(43);
+        let c: Vec<i32> = (0..16).map(|_| other.next_u32()).collect();
+        assert_ne!(a, c);
+
+        let mut floats = DefaultRng::new(42);
+        assert!((0..100).all(|_| (-1.0..=1.0).contains(&floats.next_f32())));
+    }
+}
@@ -1,4 +1,4 @@
-use build_your_own_nn::tensor::{Tensor, TensorError};
+use build_your_own_nn::tensor::{Tensor, TensorError, TensorF32, TensorF64};
 
 #[cfg(test)]
 mod tests {
@@ -23,12 +23,14 @@ mod tests {
         assert_eq!(result.unwrap_err(), TensorError::InconsistentData);
     }
 
-    // To test our self imposed restriction to allow only up to 2D
+    // To test our self imposed restriction to allow only up to 3D
     // When we'll allow more dimensions, this test should be removed
     #[test]
     fn test_rank_limits() {
-        // We currently don't support 3D tensors (Rank 3)
-        let result = Tensor::new(vec![1.0; 8], vec![2, 2, 2]);
+        // 3D tensors (Rank 3) are allowed for batches, but we don't support Rank 4
+        assert!(Tensor::new(vec![1.0; 8], vec![2, 2, 2]).is_ok());
+
+        let result = Tensor::new(vec![1.0; 16], vec![2, 2, 2, 2]);
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), TensorError::InvalidRank);
@@ -136,6 +138,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_transpose_blocked_matches_naive() -> Result<(), TensorError> {
+        for (rows, cols) in [(2, 3), (65, 33)] {
+            let data: Vec<f32> = (0..rows * cols).map(|v| v as f32).collect();
+            let a = Tensor::new(data, vec![rows, cols])?;
+
+            let naive = a.transpose_naive()?;
+            let blocked = a.transpose_blocked()?;
+
+            assert_eq!(naive, blocked);
+            assert_eq!(blocked.shape(), &[cols, rows]);
+            assert_eq!(a.transpose()?, naive);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_matmul() -> Result<(), TensorError> {
         let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2])?;
@@ -207,6 +225,608 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_get_in_bounds() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+
+        assert_eq!(a.get(0, 0), Some(1.0));
+        assert_eq!(a.get(1, 2), Some(6.0));
+        assert_eq!(a.get_flat(4), Some(5.0));
+
+        let v = Tensor::new(vec![7.0, 8.0], vec![2])?;
+        assert_eq!(v.get(0, 1), Some(8.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_out_of_bounds() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+
+        assert_eq!(a.get(2, 0), None);
+        assert_eq!(a.get(0, 3), None);
+        assert_eq!(a.get_flat(6), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_updates_data() -> Result<(), TensorError> {
+        let mut a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2])?;
+
+        a.set(1, 0, 9.0)?;
+        assert_eq!(a.data(), &[1.0, 2.0, 9.0, 4.0]);
+        assert_eq!(a.set(2, 0, 1.0), Err(TensorError::IndexOutOfBounds));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pbm_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join("byonn_test_pbm_round_trip.pbm");
+        let path = path.to_str().unwrap();
+
+        let pattern = Tensor::new(vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0], vec![2, 3])?;
+        pattern.to_pbm(path, 0.5)?;
+
+        let loaded = Tensor::from_pbm(path)?;
+        assert_eq!(loaded, pattern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_pbm_requires_2d() {
+        let path = std::env::temp_dir().join("byonn_test_to_pbm_1d.pbm");
+        let v = Tensor::new(vec![1.0, 0.0], vec![2]).unwrap();
+
+        assert!(v.to_pbm(path.to_str().unwrap(), 0.5).is_err());
+    }
+
+    #[test]
+    fn test_npy_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join("byonn_test_round_trip.npy");
+        let path = path.to_str().unwrap();
+
+        let t = Tensor::new(vec![1.0, -2.5, 3.25, 0.0, 1e-3, 42.0], vec![2, 3])?;
+        t.save_npy(path)?;
+
+        let bytes = std::fs::read(path)?;
+        assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        let header = std::str::from_utf8(&bytes[10..10 + header_len])?;
+        assert!(header.starts_with("{'descr': '<f4', 'fortran_order': False, 'shape': (2, 3), }"));
+        assert!(header.ends_with('\n'));
+        assert_eq!(bytes.len(), 10 + header_len + 6 * 4);
+
+        assert_eq!(Tensor::load_npy(path)?, t);
+
+        let v = Tensor::new(vec![1.0, 2.0], vec![2])?;
+        v.save_npy(path)?;
+        assert_eq!(Tensor::load_npy(path)?, v);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stack() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2])?;
+        let b = Tensor::new(vec![5.0, 6.0, 7.0, 8.0], vec![2, 2])?;
+        let c = Tensor::new(vec![9.0, 10.0, 11.0, 12.0], vec![2, 2])?;
+
+        let stacked = Tensor::stack(&[&a, &b, &c])?;
+
+        assert_eq!(stacked.shape(), &[3, 2, 2]);
+        assert_eq!(stacked.data()[4..8], [5.0, 6.0, 7.0, 8.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stack_errors() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2])?;
+        let b = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![4, 1])?;
+        assert_eq!(Tensor::stack(&[&a, &b]), Err(TensorError::ShapeMismatch));
+
+        let batch = Tensor::stack(&[&a, &a])?;
+        assert_eq!(Tensor::stack(&[&batch]), Err(TensorError::InvalidRank));
+        Ok(())
+    }
+
+    #[test]
+    fn test_slice_cols() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+
+        let middle = a.slice_cols(1, 2)?;
+        assert_eq!(middle.data(), &[2.0, 5.0]);
+        assert_eq!(middle.shape(), &[2, 1]);
+
+        assert_eq!(a.slice_cols(0, 3)?, a);
+        assert_eq!(a.slice_cols(1, 4), Err(TensorError::IndexOutOfBounds));
+
+        let v = Tensor::new(vec![1.0, 2.0, 3.0], vec![3])?;
+        assert_eq!(v.slice_cols(0, 1), Err(TensorError::InvalidRank));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pad_symmetric() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2])?;
+        let padded = a.pad(1, 1, 1, 1, 0.0)?;
+
+        assert_eq!(padded.shape(), &[4, 4]);
+        #[rustfmt::skip]
+        assert_eq!(padded.data(), &[
+            0.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 2.0, 0.0,
+            0.0, 3.0, 4.0, 0.0,
+            0.0, 0.0, 0.0, 0.0,
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pad_asymmetric() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+        let padded = a.pad(0, 2, 3, 1, -1.0)?;
+
+        assert_eq!(padded.shape(), &[4, 7]);
+        assert_eq!(padded.get(0, 3), Some(1.0));
+        assert_eq!(padded.get(1, 5), Some(6.0));
+        assert_eq!(padded.get(3, 6), Some(-1.0));
+
+        let v = Tensor::new(vec![1.0, 2.0], vec![2])?;
+        assert_eq!(v.pad(1, 1, 1, 1, 0.0), Err(TensorError::InvalidRank));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matmul_checked_shapes() -> Result<(), TensorError> {
+        for (m, k, n) in [
+            (1, 1, 1),
+            (1, 5, 3),
+            (4, 1, 6),
+            (7, 9, 2),
+            (16, 16, 16),
+            (3, 33, 65),
+        ] {
+            let a = Tensor::new(
+                (0..m * k).map(|i| (i % 7) as f32 - 3.0).collect(),
+                vec![m, k],
+            )?;
+            let b = Tensor::new(
+                (0..k * n).map(|i| (i % 5) as f32 * 0.5).collect(),
+                vec![k, n],
+            )?;
+
+            assert_eq!(a.matmul_checked(&b)?, a.matmul(&b)?);
+        }
+
+        let v = Tensor::new(vec![1.0, 2.0, 3.0], vec![3])?;
+        let m = Tensor::new(vec![1.0; 6], vec![3, 2])?;
+        assert_eq!(v.matmul_checked(&m)?.shape(), &[2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_matmul_cross_checks_in_debug_only() -> Result<(), TensorError> {
+        let small = Tensor::new(vec![1.0; 4], vec![2, 2])?;
+        // 65^3 multiply-adds, above the size `matmul` cross-checks
+        let big = Tensor::new(vec![1.0; 65 * 65], vec![65, 65])?;
+        let mismatched = Tensor::new(vec![1.0; 3], vec![3, 1])?;
+
+        assert_eq!(small.matmul_cross_checks(&small), cfg!(debug_assertions));
+        assert!(!big.matmul_cross_checks(&big));
+        assert!(!small.matmul_cross_checks(&mismatched));
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_matmul_agrees_accepts_zero_skip() -> Result<(), TensorError> {
+        // 0 * inf is NaN in the reference but 0 after the zero skip
+        let a = Tensor::new(vec![0.0, 1.0], vec![1, 2])?;
+        let b = Tensor::new(vec![f32::INFINITY, 2.0], vec![2, 1])?;
+        a.matmul(&b)?.assert_matmul_agrees(&a.matmul_naive(&b)?);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "where matmul_naive gives")]
+    fn test_assert_matmul_agrees_catches_nan() {
+        let naive = Tensor::new(vec![1.0, 2.0], vec![1, 2]).unwrap();
+        let tampered = Tensor::new(vec![1.0, f32::NAN], vec![1, 2]).unwrap();
+        tampered.assert_matmul_agrees(&naive);
+    }
+
+    #[test]
+    #[should_panic(expected = "matmul and matmul_naive differ")]
+    fn test_assert_matmul_agrees_catches_wrong_value() {
+        let naive = Tensor::new(vec![1.0, 2.0], vec![1, 2]).unwrap();
+        let tampered = Tensor::new(vec![1.0, 2.5], vec![1, 2]).unwrap();
+        tampered.assert_matmul_agrees(&naive);
+    }
+
+    #[test]
+    fn test_comparison_masks() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2])?;
+        let b = Tensor::new(vec![2.0, 2.0, 1.0, 5.0], vec![2, 2])?;
+
+        let mask = a.gt(&b)?;
+        assert_eq!(mask.data(), &[0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(mask.shape(), &[2, 2]);
+
+        assert_eq!(a.ge(&b)?.data(), &[0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(a.lt(&b)?.data(), &[1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(a.le(&b)?.data(), &[1.0, 1.0, 0.0, 1.0]);
+        assert_eq!(a.eq(&b)?.data(), &[0.0, 1.0, 0.0, 0.0]);
+
+        let c = Tensor::new(vec![1.0, 2.0], vec![2])?;
+        assert_eq!(a.gt(&c), Err(TensorError::ShapeMismatch));
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone_trait() -> Result<(), TensorError> {
+        fn duplicate<T: Clone>(items: &[T]) -> Vec<T> {
+            items.to_vec()
+        }
+
+        let tensors = vec![
+            Tensor::new(vec![1.0, 2.0], vec![2])?,
+            Tensor::new(vec![3.0, 4.0, 5.0, 6.0], vec![2, 2])?,
+        ];
+        let copies = duplicate(&tensors);
+        assert_eq!(copies, tensors);
+
+        let cached: Result<Tensor, TensorError> = Err(TensorError::ShapeMismatch);
+        assert_eq!(cached.clone(), cached);
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_and_zip_map() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, -2.0, 3.0, 0.5], vec![2, 2])?;
+        let b = Tensor::new(vec![4.0, 1.0, -1.0, 2.0], vec![2, 2])?;
+
+        let squared = a.map(|x| x * x)?;
+        assert_eq!(squared.data(), &[1.0, 4.0, 9.0, 0.25]);
+        assert_eq!(squared.shape(), &[2, 2]);
+
+        let larger = a.zip_map(&b, f32::max)?;
+        assert_eq!(larger.data(), &[4.0, 1.0, 3.0, 2.0]);
+
+        let c = Tensor::new(vec![1.0, 2.0], vec![2])?;
+        assert_eq!(a.zip_map(&c, |x, y| x + y), Err(TensorError::ShapeMismatch));
+        Ok(())
+    }
+
+    #[test]
+    fn test_square() -> Result<(), TensorError> {
+        let negative = Tensor::new(vec![-3.0, -0.5, -1e-3], vec![3])?;
+        let squared = negative.square()?;
+        assert_eq!(squared.data(), &[9.0, 0.25, 1e-3 * 1e-3]);
+        assert!(squared.data().iter().all(|&v| v > 0.0));
+
+        let positive = Tensor::new(vec![0.0, 1.5, 2.0, 10.0], vec![2, 2])?;
+        assert_eq!(positive.square()?, positive.powf(2.0)?);
+        assert_eq!(positive.square()?, positive.mul(&positive)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scalar_arithmetic() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, -2.0, 0.25, 4.0], vec![2, 2])?;
+
+        let added = a.add_scalar(1.5)?;
+        assert_eq!(added.data(), &[2.5, -0.5, 1.75, 5.5]);
+        assert_eq!(added.shape(), a.shape());
+
+        assert_eq!(a.sub_scalar(1.0)?.data(), &[0.0, -3.0, -0.75, 3.0]);
+        assert_eq!(a.rsub_scalar(1.0)?.data(), &[0.0, 3.0, 0.75, -3.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sigmoid_and_tanh() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![0.0, 2.0, -2.0, -200.0, 200.0], vec![5])?;
+
+        let s = a.sigmoid()?;
+        assert_eq!(s.shape(), a.shape());
+        assert_eq!(s.data()[0], 0.5);
+        assert!((s.data()[1] - 0.880797).abs() < 1e-6);
+        assert!((s.data()[1] + s.data()[2] - 1.0).abs() < 1e-6);
+        // Large logits saturate instead of overflowing to NaN
+        assert_eq!(&s.data()[3..], &[0.0, 1.0]);
+
+        let t = a.tanh()?;
+        assert!((t.data()[1] - 0.964028).abs() < 1e-6);
+        assert_eq!(t.data()[2], -t.data()[1]);
+        assert_eq!(&t.data()[3..], &[-1.0, 1.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rows() -> Result<(), TensorError> {
+        let t = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![3, 2])?;
+
+        let rows: Vec<&[f32]> = t.rows().collect();
+        assert_eq!(rows, vec![&[1.0, 2.0][..], &[3.0, 4.0], &[5.0, 6.0]]);
+
+        assert_eq!(t.row(1), Some(&[3.0, 4.0][..]));
+        assert_eq!(t.row(3), None);
+
+        let v = Tensor::new(vec![1.0, 2.0, 3.0], vec![3])?;
+        assert_eq!(v.rows().count(), 1);
+
+        let no_cols = Tensor::new(vec![], vec![2, 0])?;
+        assert_eq!(no_cols.rows().count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_flatten() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+        let flat = a.flatten();
+        assert_eq!(flat.shape(), &[6]);
+        assert_eq!(flat.data(), a.data());
+
+        let batch = Tensor::new((0..8).map(|i| i as f32).collect(), vec![2, 2, 2])?;
+        assert_eq!(batch.flatten().shape(), &[8]);
+
+        let v = Tensor::new(vec![1.0, 2.0, 3.0], vec![3])?;
+        assert_eq!(v.flatten(), v);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reshape_compatible() -> Result<(), TensorError> {
+        let v = Tensor::new(vec![1.0, 2.0, 3.0], vec![3])?;
+
+        let column = v.reshape_compatible(&[3, 1])?;
+        assert_eq!(column.shape(), &[3, 1]);
+        assert_eq!(column.data(), v.data());
+        assert_eq!(column.reshape_compatible(&[3])?, v);
+        assert_eq!(v.reshape_compatible(&[1, 3])?.shape(), &[1, 3]);
+
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+        assert_eq!(
+            a.reshape_compatible(&[3, 2]),
+            Err(TensorError::ShapeMismatch)
+        );
+        assert_eq!(a.reshape_compatible(&[6]), Err(TensorError::ShapeMismatch));
+        assert_eq!(
+            v.reshape_compatible(&[4, 1]),
+            Err(TensorError::ShapeMismatch)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_percentile() -> Result<(), TensorError> {
+        let odd = Tensor::new(vec![7.0, 1.0, 5.0, 3.0, 9.0], vec![5])?;
+        assert_eq!(odd.percentile(50.0), 5.0);
+        assert_eq!(odd.percentile(0.0), 1.0);
+        assert_eq!(odd.percentile(100.0), 9.0);
+        // Rank 0.25 * 4 = 1 lands on the second smallest element
+        assert_eq!(odd.percentile(25.0), 3.0);
+
+        // An even count interpolates the median between the middle two
+        let even = Tensor::new(vec![4.0, 1.0, 2.0, 3.0], vec![2, 2])?;
+        assert_eq!(even.percentile(50.0), 2.5);
+        assert_eq!(even.percentile(150.0), 4.0);
+
+        assert!(Tensor::empty().percentile(50.0).is_nan());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cumsum() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+
+        let down = a.cumsum(0)?;
+        assert_eq!(down.shape(), &[2, 3]);
+        assert_eq!(down.data(), &[1.0, 2.0, 3.0, 5.0, 7.0, 9.0]);
+
+        let across = a.cumsum(1)?;
+        assert_eq!(across.shape(), &[2, 3]);
+        assert_eq!(across.data(), &[1.0, 3.0, 6.0, 4.0, 9.0, 15.0]);
+
+        assert_eq!(
+            a.flatten().cumsum(0)?.data(),
+            &[1.0, 3.0, 6.0, 10.0, 15.0, 21.0]
+        );
+        assert_eq!(a.cumsum(2), Err(TensorError::InvalidRank));
+        assert_eq!(a.flatten().cumsum(1), Err(TensorError::InvalidRank));
+        Ok(())
+    }
+
+    #[test]
+    fn test_histogram() -> Result<(), TensorError> {
+        // Four bins of width 0.5 over [0, 2], plus values on both sides of the range
+        let values = vec![
+            -3.0,
+            0.0,
+            0.1,
+            0.49,
+            0.5,
+            0.9,
+            1.2,
+            1.99,
+            2.0,
+            7.0,
+            f32::NAN,
+        ];
+        let t = Tensor::new(values, vec![11])?;
+
+        assert_eq!(t.histogram(4, (0.0, 2.0)), vec![4, 2, 1, 3]);
+        assert_eq!(t.histogram(1, (0.0, 2.0)), vec![10]);
+        assert!(t.histogram(0, (0.0, 2.0)).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_flip() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+
+        let h = a.flip_horizontal()?;
+        assert_eq!(h.shape(), &[2, 3]);
+        assert_eq!(h.data(), &[3.0, 2.0, 1.0, 6.0, 5.0, 4.0]);
+        assert_eq!(h.get(0, 0), a.get(0, 2));
+
+        let v = a.flip_vertical()?;
+        assert_eq!(v.shape(), &[2, 3]);
+        assert_eq!(v.data(), &[4.0, 5.0, 6.0, 1.0, 2.0, 3.0]);
+        assert_eq!(v.get(1, 2), a.get(0, 2));
+
+        assert_eq!(h.flip_horizontal()?, a);
+        assert_eq!(a.flatten().flip_vertical(), Err(TensorError::InvalidRank));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tile() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2])?;
+
+        let down = a.tile(2, 1)?;
+        assert_eq!(down.shape(), &[4, 2]);
+        assert_eq!(down.data(), &[1.0, 2.0, 3.0, 4.0, 1.0, 2.0, 3.0, 4.0]);
+
+        let across = a.tile(1, 2)?;
+        assert_eq!(across.shape(), &[2, 4]);
+        assert_eq!(across.data(), &[1.0, 2.0, 1.0, 2.0, 3.0, 4.0, 3.0, 4.0]);
+
+        let row = Tensor::new(vec![0.5, 0.25, 1.0], vec![1, 3])?;
+        assert_eq!(row.tile(5, 1)?.shape(), &[5, 3]);
+
+        let v = Tensor::new(vec![1.0, 2.0], vec![2])?;
+        assert_eq!(v.tile(2, 2), Err(TensorError::InvalidRank));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fill_and_zero() -> Result<(), TensorError> {
+        let mut t = Tensor::new(vec![1.0, -2.0, 3.5, 4.0, 0.5, 6.0], vec![2, 3])?;
+
+        t.fill(3.0);
+        assert_eq!(t.shape(), &[2, 3]);
+        assert!(t.data().iter().all(|&v| v == 3.0));
+
+        t.zero_();
+        assert_eq!(t, Tensor::new(vec![0.0; 6], vec![2, 3])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scale_rows() -> Result<(), TensorError> {
+        let m = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![3, 2])?;
+        let expected = Tensor::new(vec![1.0, 2.0, 6.0, 8.0, 15.0, 18.0], vec![3, 2])?;
+
+        let flat = Tensor::new(vec![1.0, 2.0, 3.0], vec![3])?;
+        assert_eq!(m.scale_rows(&flat)?, expected);
+        let column = Tensor::new(vec![1.0, 2.0, 3.0], vec![3, 1])?;
+        assert_eq!(m.scale_rows(&column)?, expected);
+
+        let short = Tensor::new(vec![1.0, 2.0], vec![2])?;
+        assert_eq!(m.scale_rows(&short), Err(TensorError::ShapeMismatch));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sub_row_vector() -> Result<(), TensorError> {
+        let m = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![3, 2])?;
+        let expected = Tensor::new(vec![-2.0, -2.0, 0.0, 0.0, 2.0, 2.0], vec![3, 2])?;
+
+        // Column means, so the result is centered
+        let flat = Tensor::new(vec![3.0, 4.0], vec![2])?;
+        assert_eq!(m.sub_row_vector(&flat)?, expected);
+        let row = Tensor::new(vec![3.0, 4.0], vec![1, 2])?;
+        assert_eq!(m.sub_row_vector(&row)?, expected);
+
+        let wide = Tensor::new(vec![1.0, 2.0, 3.0], vec![3])?;
+        assert_eq!(m.sub_row_vector(&wide), Err(TensorError::ShapeMismatch));
+        let column = Tensor::new(vec![3.0, 4.0], vec![2, 1])?;
+        assert_eq!(m.sub_row_vector(&column), Err(TensorError::ShapeMismatch));
+        Ok(())
+    }
+
+    #[test]
+    fn test_diag_and_trace() -> Result<(), TensorError> {
+        let m = Tensor::new(
+            vec![2.0, 9.0, 9.0, 9.0, -1.0, 9.0, 9.0, 9.0, 4.5],
+            vec![3, 3],
+        )?;
+
+        assert_eq!(m.diag()?, Tensor::new(vec![2.0, -1.0, 4.5], vec![3])?);
+        assert_eq!(m.trace()?, Tensor::new(vec![5.5], vec![1])?);
+
+        let wide = Tensor::new(vec![0.0; 6], vec![2, 3])?;
+        assert_eq!(wide.diag(), Err(TensorError::ShapeMismatch));
+        assert_eq!(wide.trace(), Err(TensorError::ShapeMismatch));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matmul_strict_propagates_nan() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![0.0, 1.0], vec![1, 2])?;
+        let b = Tensor::new(vec![f32::NAN, 2.0], vec![2, 1])?;
+
+        // The zero skip never multiplies the NaN row, IEEE says 0 * NaN = NaN
+        assert_eq!(a.matmul(&b)?.data(), &[2.0]);
+        assert!(a.matmul_strict(&b)?.data()[0].is_nan());
+        assert!(a.matmul_naive(&b)?.data()[0].is_nan());
+
+        let x = Tensor::new(vec![1.0, 0.0, 2.0, 3.0, 4.0, 0.5], vec![2, 3])?;
+        let y = Tensor::new(vec![1.0, -1.0, 0.0, 2.0, 0.25, 4.0], vec![3, 2])?;
+        assert_eq!(x.matmul_strict(&y)?, x.matmul(&y)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_batched_matmul() -> Result<(), TensorError> {
+        // b = 2, m = 2, k = 3, n = 2
+        let a = Tensor::new((0..12).map(|i| i as f32 - 4.0).collect(), vec![2, 2, 3])?;
+        let b = Tensor::new((0..12).map(|i| 0.5 * i as f32).collect(), vec![2, 3, 2])?;
+
+        let batched = a.batched_matmul(&b)?;
+        assert_eq!(batched.shape(), &[2, 2, 2]);
+
+        for i in 0..2 {
+            let a_i = Tensor::new(a.data()[i * 6..(i + 1) * 6].to_vec(), vec![2, 3])?;
+            let b_i = Tensor::new(b.data()[i * 6..(i + 1) * 6].to_vec(), vec![3, 2])?;
+            assert_eq!(
+                &batched.data()[i * 4..(i + 1) * 4],
+                a_i.matmul(&b_i)?.data()
+            );
+        }
+
+        let other_batch = Tensor::new(vec![0.0; 18], vec![3, 3, 2])?;
+        assert_eq!(
+            a.batched_matmul(&other_batch),
+            Err(TensorError::ShapeMismatch)
+        );
+        let other_inner = Tensor::new(vec![0.0; 8], vec![2, 2, 2])?;
+        assert_eq!(
+            a.batched_matmul(&other_inner),
+            Err(TensorError::ShapeMismatch)
+        );
+        let flat = Tensor::new(vec![0.0; 6], vec![3, 2])?;
+        assert_eq!(a.batched_matmul(&flat), Err(TensorError::InvalidRank));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matmul_into() -> Result<(), TensorError> {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+        let b = Tensor::new(vec![7.0, 8.0, 9.0, 10.0, 11.0, 12.0], vec![3, 2])?;
+
+        // Stale values in the buffer must not leak into the result
+        let mut out = Tensor::new(vec![100.0; 4], vec![2, 2])?;
+        a.matmul_into(&b, &mut out)?;
+        assert_eq!(out, a.matmul(&b)?);
+
+        let mut wrong = Tensor::new(vec![0.0; 6], vec![3, 2])?;
+        assert_eq!(
+            a.matmul_into(&b, &mut wrong),
+            Err(TensorError::ShapeMismatch)
+        );
+        Ok(())
+    }
+
     fn setup_matrix_for_reduction() -> Tensor {
         let data = vec![
             1000.0, 2000.0, 3000.0, 1200.0, 1800.0, 2000.0, 1500.0, 2500.0, 2200.0,
@@ -265,4 +885,214 @@ mod tests {
 
         assert_eq!(res.err(), Some(TensorError::InvalidRank));
     }
+
+    #[test]
+    fn test_zero_sized_shapes() -> Result<(), TensorError> {
+        // Zero-sized dimensions are allowed, reductions over them are 0
+        let empty = Tensor::new(vec![], vec![0])?;
+        assert_eq!(empty.sum(None)?.data(), &[0.0]);
+        assert_eq!(empty.sum_kahan(None)?.data(), &[0.0]);
+
+        let no_cols = Tensor::new(vec![], vec![2, 0])?;
+        assert_eq!(no_cols.sum(Some(0))?.shape(), &[0]);
+        assert_eq!(no_cols.sum(Some(1))?.data(), &[0.0, 0.0]);
+        assert_eq!(no_cols.sum_kahan(Some(1))?.data(), &[0.0, 0.0]);
+
+        let inner = Tensor::new(vec![], vec![0, 3])?;
+        let product = no_cols.matmul(&inner)?;
+        assert_eq!(product.shape(), &[2, 3]);
+        assert!(product.data().iter().all(|&v| v == 0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sum_kahan_limits_drift() -> Result<(), TensorError> {
+        let n = 1_000_000;
+        let tensor = Tensor::new(vec![0.1; n], vec![n])?;
+        let exact = 100_000.0;
+
+        let naive = tensor.sum(None)?.data()[0];
+        let kahan = tensor.sum_kahan(None)?.data()[0];
+
+        assert!((naive - exact).abs() > 100.0);
+        assert!((kahan - exact).abs() < 0.01);
+        Ok(())
+    }
+
+    #[test]
+    fn test_product() -> Result<(), TensorError> {
+        let t = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+
+        assert_eq!(t.product(None)?, Tensor::new(vec![720.0], vec![1])?);
+        assert_eq!(
+            t.product(Some(0))?,
+            Tensor::new(vec![4.0, 10.0, 18.0], vec![3])?
+        );
+        assert_eq!(t.product(Some(1))?, Tensor::new(vec![6.0, 120.0], vec![2])?);
+        assert_eq!(t.product(Some(2)), Err(TensorError::InvalidRank));
+
+        // A 1D tensor takes the same axes as `sum`
+        let v = Tensor::new(vec![2.0, 3.0], vec![2])?;
+        assert_eq!(v.product(Some(1))?.data(), &[6.0]);
+        assert_eq!(v.product(Some(7)), Err(TensorError::InvalidRank));
+
+        // Nothing to multiply leaves the empty product
+        let no_cols = Tensor::new(vec![], vec![2, 0])?;
+        assert_eq!(no_cols.product(None)?.data(), &[1.0]);
+        assert_eq!(no_cols.product(Some(1))?.data(), &[1.0, 1.0]);
+        assert_eq!(no_cols.product(Some(0))?.shape(), &[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_logsumexp() -> Result<(), TensorError> {
+        let m = Tensor::new(vec![0.5, -1.0, 2.0, 1.0, 0.0, -0.5], vec![2, 3])?;
+        let naive = |values: &[f32]| values.iter().map(|v| v.exp()).sum::<f32>().ln();
+        let close = |a: &[f32], b: &[f32]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5);
+
+        let all = m.logsumexp(None)?;
+        assert_eq!(all.shape(), &[1]);
+        assert!(close(all.data(), &[naive(m.data())]));
+
+        let rows = m.logsumexp(Some(1))?;
+        assert_eq!(rows.shape(), &[2]);
+        assert!(close(
+            rows.data(),
+            &[naive(&[0.5, -1.0, 2.0]), naive(&[1.0, 0.0, -0.5])]
+        ));
+
+        let cols = m.logsumexp(Some(0))?;
+        assert_eq!(cols.shape(), &[3]);
+        assert!(close(
+            cols.data(),
+            &[naive(&[0.5, 1.0]), naive(&[-1.0, 0.0]), naive(&[2.0, -0.5])]
+        ));
+
+        // exp(1000) overflows, the shifted form does not
+        let large = Tensor::new(vec![1000.0, 1001.0], vec![2])?;
+        assert!(naive(large.data()).is_infinite());
+        let lse = large.logsumexp(None)?.data()[0];
+        assert!((lse - (1001.0 + (1.0 + (-1.0f32).exp()).ln())).abs() < 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_and_argmin() -> Result<(), TensorError> {
+        // Column 1 has a tie, the first occurrence wins
+        let t = Tensor::new(vec![3.0, -1.0, 4.0, 1.0, -1.0, 9.0], vec![2, 3])?;
+
+        assert_eq!(t.min(None)?, Tensor::new(vec![-1.0], vec![1])?);
+        assert_eq!(t.argmin(None)?, vec![1]);
+
+        assert_eq!(t.min(Some(0))?, Tensor::new(vec![1.0, -1.0, 4.0], vec![3])?);
+        assert_eq!(t.argmin(Some(0))?, vec![1, 0, 0]);
+
+        assert_eq!(t.min(Some(1))?, Tensor::new(vec![-1.0, -1.0], vec![2])?);
+        assert_eq!(t.argmin(Some(1))?, vec![1, 1]);
+
+        assert_eq!(t.min(Some(2)), Err(TensorError::InvalidRank));
+
+        // A 1D tensor takes the same axes as `sum`, in every lane reduction
+        let v = Tensor::new(vec![2.0, -3.0], vec![2])?;
+        assert_eq!(v.min(Some(1))?.data(), &[-3.0]);
+        assert_eq!(v.min(Some(7)), Err(TensorError::InvalidRank));
+        assert_eq!(v.argmin(Some(7)), Err(TensorError::InvalidRank));
+        assert_eq!(v.logsumexp(Some(7)), Err(TensorError::InvalidRank));
+
+        let no_rows = Tensor::new(vec![], vec![0, 3])?;
+        assert_eq!(no_rows.min(Some(0)), Err(TensorError::ShapeMismatch));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sum_keepdims() -> Result<(), TensorError> {
+        let tensor = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+
+        let cols = tensor.sum_keepdims(0)?;
+        assert_eq!(cols.shape(), &[1, 3]);
+        assert_eq!(cols.data(), tensor.sum(Some(0))?.data());
+
+        let rows = tensor.sum_keepdims(1)?;
+        assert_eq!(rows.shape(), &[2, 1]);
+        assert_eq!(rows.data(), tensor.sum(Some(1))?.data());
+
+        assert_eq!(tensor.sum_keepdims(2), Err(TensorError::InvalidRank));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sum_kahan_axes() -> Result<(), TensorError> {
+        let tensor = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3])?;
+
+        assert_eq!(tensor.sum_kahan(Some(0))?, tensor.sum(Some(0))?);
+        assert_eq!(tensor.sum_kahan(Some(1))?, tensor.sum(Some(1))?);
+        assert_eq!(tensor.sum_kahan(Some(2)), Err(TensorError::InvalidRank));
+        Ok(())
+    }
+
+    #[test]
+    fn test_f32_and_f64_tensors_multiply() -> Result<(), TensorError> {
+        let a = TensorF32::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2])?;
+        let b = TensorF32::new(vec![5.0, 6.0, 7.0, 8.0], vec![2, 2])?;
+        assert_eq!(a.matmul_naive(&b)?.data(), &[19.0, 22.0, 43.0, 50.0]);
+        assert_eq!(a.mul(&b)?.data(), &[5.0, 12.0, 21.0, 32.0]);
+
+        // Same product in f64, with a value f32 cannot represent exactly
+        let c = TensorF64::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2])?;
+        let d = TensorF64::new(vec![5.0, 6.0, 7.0, 8.0 + 1e-12], vec![2, 2])?;
+        let product = c.matmul_naive(&d)?;
+        assert_eq!(product.shape(), &[2, 2]);
+        assert_eq!(product.data()[0], 19.0);
+        assert!(product.data()[3] > 50.0);
+        assert_eq!(c.mul(&d)?.data()[..3], [5.0, 12.0, 21.0]);
+
+        // The alias keeps the existing f32 API
+        let t: Tensor = a;
+        assert_eq!(t.matmul(&b)?, t.matmul_naive(&b)?);
+        Ok(())
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_matmul_simd_matches_naive() -> Result<(), TensorError> {
+        // Column counts below, between and above multiples of 8 exercise the scalar remainder
+        for (rows, inner, cols) in [(1, 3, 5), (4, 7, 9), (3, 16, 13), (5, 2, 17), (2, 8, 8)] {
+            let a: Vec<f32> = (0..rows * inner).map(|i| (i % 7) as f32 - 3.0).collect();
+            let b: Vec<f32> = (0..inner * cols).map(|i| (i % 5) as f32 * 0.5).collect();
+            let a = Tensor::new(a, vec![rows, inner])?;
+            let b = Tensor::new(b, vec![inner, cols])?;
+
+            let simd = a.matmul_simd(&b)?;
+            let naive = a.matmul_naive(&b)?;
+            assert_eq!(simd.shape(), naive.shape());
+            for (x, y) in simd.data().iter().zip(naive.data()) {
+                assert!((x - y).abs() < 1e-5, "{} vs {}", x, y);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_softmax_rows() -> Result<(), TensorError> {
+        let logits = Tensor::new(vec![1.0, 3.0, 2.0, 1000.0, -5.0, 999.0], vec![2, 3])?;
+        let probs = logits.softmax_rows()?;
+        assert_eq!(probs.shape(), &[2, 3]);
+
+        for (p_row, x_row) in probs.rows().zip(logits.rows()) {
+            assert!((p_row.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+            assert!(p_row.iter().all(|p| p.is_finite()));
+            // Larger logits keep the larger probability
+            for i in 0..3 {
+                for j in 0..3 {
+                    if x_row[i] > x_row[j] {
+                        assert!(p_row[i] > p_row[j]);
+                    }
+                }
+            }
+        }
+
+        let flat = Tensor::new(vec![1.0, 2.0], vec![2])?;
+        assert_eq!(flat.softmax_rows(), Err(TensorError::InvalidRank));
+        Ok(())
+    }
 }